@@ -0,0 +1,52 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use open_tick::import::collect_ticks;
+use open_tick::Source;
+
+const MP_HEADER: &str = r#"Date,Route,Rating,Notes,URL,Pitches,Location,"Avg Stars","Your Stars",Style,"Lead Style","Route Type","Your Rating",Length,"Rating Code""#;
+
+const MP_ROWS: [&str; 3] = [
+    r#"2023-06-01,"Route Name",V1,,https://www.mountainproject.com/route/271828/route-name,1,"Area > Crag",2.5,-1,Send,,Boulder,,10,20300"#,
+    r#"2023-05-20,"Classic Crack",5.9,"Great jams, bring big cams",https://www.mountainproject.com/route/105748391/classic-crack,2,"Colorado > Eldorado Canyon SP > Redgarden Wall",3.4,4,Lead,Onsight,Trad,,300,2900"#,
+    r#"2023-05-19,"Bolt Line",5.11a,,https://www.mountainproject.com/route/314159/bolt-line,1,"Colorado > Boulder Canyon > Animal World",2.9,3,Lead,Redpoint,"Sport, TR",5.11b,80,4100"#,
+];
+
+const CRAG_HEADER: &str = "Route Name,Ascent Label,Ascent ID,Ascent Link,Ascent Type,Route Grade,Ascent Grade,Route Gear Style,Ascent Gear Style,Route Height,Ascent Height,# Ascents,Route Stars,Route ID,Route Link,Country,Country Link,Crag Name,Crag Link,Crag Path,With,Comment,Quality,Ascent Date,Log Date,Shot";
+
+const CRAG_ROWS: [&str; 2] = [
+    "Ossis Ultima,Red point of Ossis Ultima,5069547315,https://www.thecrag.com/ascent/5069547315,Red point,24,24,Sport,Sport,25,25,1,3,12345,https://www.thecrag.com/route/12345,Australia,https://www.thecrag.com/climbing/australia,Arapiles,https://www.thecrag.com/climbing/australia/arapiles,Australia > Victoria > Arapiles,Jo Bloggs,Pumpy,,2023-05-30T00:00:00Z,2023-05-31T10:11:12Z,",
+    "Kachoong,Onsight of Kachoong,5069547316,https://www.thecrag.com/ascent/5069547316,Onsight,21,,Trad,Trad,30,30,1,3,12346,https://www.thecrag.com/route/12346,Australia,https://www.thecrag.com/climbing/australia,Arapiles,https://www.thecrag.com/climbing/australia/arapiles,Australia > Victoria > Arapiles,,,,2023-05-29T00:00:00Z,2023-05-31T10:11:12Z,1",
+];
+
+/// A synthetic export with `n` rows, cycling through `rows`
+fn export(header: &str, rows: &[&str], n: usize) -> String {
+    let mut data = String::from(header);
+    data.push('\n');
+    for row in rows.iter().cycle().take(n) {
+        data.push_str(row);
+        data.push('\n');
+    }
+    data
+}
+
+fn import(c: &mut Criterion) {
+    let mut group = c.benchmark_group("collect_ticks");
+
+    for n in [1_000, 50_000] {
+        group.throughput(Throughput::Elements(n as u64));
+
+        let mp = export(MP_HEADER, &MP_ROWS, n);
+        group.bench_with_input(BenchmarkId::new("mountain_project", n), &mp, |b, data| {
+            b.iter(|| collect_ticks(data.as_bytes(), Source::MountainProject).unwrap())
+        });
+
+        let crag = export(CRAG_HEADER, &CRAG_ROWS, n);
+        group.bench_with_input(BenchmarkId::new("thecrag", n), &crag, |b, data| {
+            b.iter(|| collect_ticks(data.as_bytes(), Source::TheCrag).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, import);
+criterion_main!(benches);
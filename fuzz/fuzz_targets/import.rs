@@ -0,0 +1,36 @@
+//! Import arbitrary bytes as an export, checking that malformed input is an error, not a panic
+//!
+//! The first byte picks the platform, and the rest is read as its CSV export.  Run with
+//! `cargo +nightly fuzz run import fuzz/corpus/import fuzz/seeds/import` from the repository
+//! root.  The seeds are the CSV test fixtures and the `malformed_cells` rows, each behind its
+//! platform's byte.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use open_tick::grade::GradePreferences;
+use open_tick::import::collect_ticks_lossy;
+use open_tick::Source;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&selector, csv)) = data.split_first() else {
+        return;
+    };
+    let source = match selector % 3 {
+        0 => Source::MountainProject,
+        1 => Source::TheCrag,
+        _ => Source::VerticalLife,
+    };
+
+    let (ticks, _errors) = collect_ticks_lossy(csv, source);
+
+    // Converted ticks hold whatever text the export had, so the helpers that parse it again
+    // must cope with it too
+    for tick in ticks {
+        let _ = tick.to_string();
+        let _ = tick.route_id();
+        let _ = tick.normalized_grade(&GradePreferences::default());
+        let _ = tick.grade_parse_status();
+        let _ = tick.sandbagged();
+    }
+});
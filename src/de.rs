@@ -0,0 +1,53 @@
+use serde::de::{self, DeserializeOwned, Deserializer, IntoDeserializer};
+use serde::Deserialize;
+
+/// Enums with a catch-all variant for values a platform may add in future
+pub(crate) trait OrOther: DeserializeOwned {
+    /// The catch-all variant holding `value`
+    fn other(value: String) -> Self;
+
+    /// The value held by the catch-all variant, if that's what this is
+    fn as_other(&self) -> Option<&str>;
+}
+
+/// Records that may hold values not known to this crate
+pub(crate) trait Unrecognized {
+    /// The first value held by a catch-all variant, if any
+    fn unrecognized_value(&self) -> Option<&str>;
+}
+
+/// Parse a known variant, falling back to the catch-all variant
+pub(crate) fn parse_or_other<T: OrOther>(value: String) -> T {
+    T::deserialize(value.as_str().into_deserializer())
+        .unwrap_or_else(|_: de::value::Error| T::other(value))
+}
+
+/// Deserialize a known variant, falling back to the catch-all variant
+pub(crate) fn or_other<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: OrOther,
+{
+    String::deserialize(deserializer).map(parse_or_other)
+}
+
+/// As [`or_other`], with an empty value deserializing to `None`
+pub(crate) fn option_or_other<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: OrOther,
+{
+    Option::<String>::deserialize(deserializer)
+        .map(|value| value.filter(|v| !v.is_empty()).map(parse_or_other))
+}
+
+/// Deserialize a URL, with an empty or malformed value deserializing to `None`
+///
+/// For links that are only informational, so a bad one shouldn't cost the whole record.
+pub(crate) fn lenient_url<'de, D>(deserializer: D) -> Result<Option<url::Url>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)
+        .map(|value| value.and_then(|v| url::Url::parse(v.trim()).ok()))
+}
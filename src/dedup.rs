@@ -0,0 +1,154 @@
+//! Finding ticks logged more than once
+//!
+//! Climbers who log on several platforms often record the same ascent on each.  This module
+//! only finds such duplicates, so that a user can decide how to resolve them; see
+//! [`OpenTick::merge`] and [`Logbook::dedup`](crate::logbook::Logbook::dedup) for merging them.
+
+use crate::{util, OpenTick, PartialDate};
+
+/// Pairs of indices into `ticks` that record the same ascent
+///
+/// Ticks are compared with [`OpenTick::same_ascent`].  Each pair is ordered, with the smaller
+/// index first, and pairs are sorted.  Every pair of duplicates is reported, so an ascent logged
+/// three times gives three pairs.  Ticks without a route name are skipped, since there is no
+/// telling whether two unnamed climbs on the same day were the same one.
+///
+/// # Examples
+/// ```
+/// use open_tick::dedup::find_duplicates;
+/// use open_tick::OpenTick;
+///
+/// let mut tick = OpenTick::default();
+/// tick.route_name = Some("The Nose".to_string());
+///
+/// let ticks = [tick.clone(), OpenTick::default(), tick];
+/// assert_eq!(find_duplicates(&ticks), [(0, 2)]);
+/// ```
+pub fn find_duplicates(ticks: &[OpenTick]) -> Vec<(usize, usize)> {
+    let named: Vec<(usize, &OpenTick)> = ticks
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| t.route_name.is_some())
+        .collect();
+
+    named
+        .iter()
+        .enumerate()
+        .flat_map(|(n, &(i, a))| {
+            named[n + 1..]
+                .iter()
+                .filter(move |(_, b)| a.same_ascent(b))
+                .map(move |&(j, _)| (i, j))
+        })
+        .collect()
+}
+
+/// Confidence from 0 to 1 that two ticks record the same ascent
+///
+/// The [similarity](util::name_similarity) of the route names is scaled down when the dates or
+/// locations differ:
+/// - by half for dates a day apart, as a timezone can shift a date, and to zero for dates
+///   further apart or when only one tick is dated.  Ticks dated only to the year or month must
+///   have the same partial date;
+/// - by half when both ticks have a crag and they differ, since platforms name crags
+///   differently.
+///
+/// Ticks that [`OpenTick::same_ascent`] finds the same score 1.  Ticks without a route name
+/// score 0.
+///
+/// # Examples
+/// ```
+/// use open_tick::dedup::match_score;
+/// use open_tick::OpenTick;
+///
+/// let mut a = OpenTick::default();
+/// a.route_name = Some("The Nose".to_string());
+/// let mut b = OpenTick::default();
+/// b.route_name = Some("Nose, The".to_string());
+///
+/// assert_eq!(match_score(&a, &b), 1.0);
+/// ```
+pub fn match_score(a: &OpenTick, b: &OpenTick) -> f32 {
+    let (Some(name_a), Some(name_b)) = (&a.route_name, &b.route_name) else {
+        return 0.0;
+    };
+
+    let date = |tick: &OpenTick| tick.date.map(PartialDate::Full).or(tick.partial_date);
+    let dates = match (date(a), date(b)) {
+        (Some(PartialDate::Full(x)), Some(PartialDate::Full(y))) => {
+            match (x - y).num_days().abs() {
+                0 => 1.0,
+                1 => 0.5,
+                _ => 0.0,
+            }
+        }
+        (x, y) if x == y => 1.0,
+        _ => 0.0,
+    };
+
+    let locations = match (a.normalized_crag(), b.normalized_crag()) {
+        (Some(x), Some(y)) if x != y => 0.5,
+        _ => 1.0,
+    };
+
+    util::name_similarity(name_a, name_b) * dates * locations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Source;
+    use chrono::NaiveDate;
+    use pretty_assertions::assert_eq;
+
+    fn tick(day: u32, name: &str, source: Source) -> OpenTick {
+        OpenTick {
+            date: NaiveDate::from_ymd_opt(2023, 6, day),
+            route_name: Some(name.to_string()),
+            source: Some(source),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn duplicate_across_sources() {
+        let ticks = [
+            tick(1, "Classic Crack", Source::MountainProject),
+            tick(1, "Other Route", Source::MountainProject),
+            tick(2, "Classic Crack", Source::TheCrag),
+            tick(1, "classic crack", Source::TheCrag),
+        ];
+
+        assert_eq!(find_duplicates(&ticks), [(0, 3)]);
+    }
+
+    #[test]
+    fn near_matches_score_high() {
+        let a = tick(1, "Classic Crack", Source::MountainProject);
+        let typo = tick(1, "Clasic Crack", Source::TheCrag);
+        let next_day = tick(2, "Classic Crack", Source::TheCrag);
+        let unrelated = tick(20, "Something Else", Source::TheCrag);
+
+        assert_eq!(match_score(&a, &a.clone()), 1.0);
+        assert!(match_score(&a, &typo) > 0.9);
+        assert!(match_score(&a, &next_day) >= 0.5);
+        assert_eq!(match_score(&a, &unrelated), 0.0);
+        assert!(
+            match_score(&a, &tick(1, "Something Else", Source::TheCrag)) < 0.5,
+            "same day, different route"
+        );
+    }
+
+    #[test]
+    fn partial_dates_must_match() {
+        let year = |y| OpenTick {
+            date: None,
+            partial_date: Some(PartialDate::Year(y)),
+            ..tick(1, "Classic Crack", Source::VerticalLife)
+        };
+
+        assert_eq!(match_score(&year(2019), &year(2019)), 1.0);
+        assert_eq!(match_score(&year(2019), &year(2021)), 0.0);
+        assert_eq!(find_duplicates(&[year(2019), year(2021)]), []);
+    }
+}
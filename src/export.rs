@@ -0,0 +1,538 @@
+use crate::{
+    AscentStyle, ConversionError, Discipline, DisciplineKind, OpenTick, PartialDate, TheCragTick,
+};
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::fmt;
+use std::io::Write;
+
+/// Errors that can occur while exporting ticks
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum ExportError {
+    /// The export could not be written
+    Csv(csv::Error),
+    /// A tick could not be converted to the export format
+    Conversion(ConversionError),
+    /// The JSON export could not be written
+    Json(serde_json::Error),
+    /// The TOML export, serialized in full first, could not be written out
+    Io(std::io::Error),
+    /// The ticks could not be written as TOML
+    #[cfg(feature = "toml")]
+    Toml(toml::ser::Error),
+    /// The ticks could not be written as YAML
+    #[cfg(feature = "yaml")]
+    Yaml(serde_norway::Error),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExportError::Csv(e) => write!(f, "could not write tick: {e}"),
+            ExportError::Conversion(e) => write!(f, "could not convert tick: {e}"),
+            ExportError::Json(e) => write!(f, "could not write ticks: {e}"),
+            ExportError::Io(e) => write!(f, "could not write ticks: {e}"),
+            #[cfg(feature = "toml")]
+            ExportError::Toml(e) => write!(f, "could not write ticks as TOML: {e}"),
+            #[cfg(feature = "yaml")]
+            ExportError::Yaml(e) => write!(f, "could not write ticks as YAML: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ExportError::Csv(e) => Some(e),
+            ExportError::Conversion(e) => Some(e),
+            ExportError::Json(e) => Some(e),
+            ExportError::Io(e) => Some(e),
+            #[cfg(feature = "toml")]
+            ExportError::Toml(e) => Some(e),
+            #[cfg(feature = "yaml")]
+            ExportError::Yaml(e) => Some(e),
+        }
+    }
+}
+
+impl From<csv::Error> for ExportError {
+    fn from(value: csv::Error) -> Self {
+        ExportError::Csv(value)
+    }
+}
+
+impl From<ConversionError> for ExportError {
+    fn from(value: ConversionError) -> Self {
+        ExportError::Conversion(value)
+    }
+}
+
+impl From<serde_json::Error> for ExportError {
+    fn from(value: serde_json::Error) -> Self {
+        ExportError::Json(value)
+    }
+}
+
+impl From<std::io::Error> for ExportError {
+    fn from(value: std::io::Error) -> Self {
+        ExportError::Io(value)
+    }
+}
+
+#[cfg(feature = "toml")]
+impl From<toml::ser::Error> for ExportError {
+    fn from(value: toml::ser::Error) -> Self {
+        ExportError::Toml(value)
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl From<serde_norway::Error> for ExportError {
+    fn from(value: serde_norway::Error) -> Self {
+        ExportError::Yaml(value)
+    }
+}
+
+/// Write ticks as a CSV that can be imported to theCrag
+///
+/// The columns match theCrag's own logbook export.  Fields that only theCrag can assign, such as
+/// IDs and links, are left blank.
+pub fn to_thecrag_csv<W: Write>(ticks: &[OpenTick], w: W) -> Result<(), ExportError> {
+    let mut writer = csv::Writer::from_writer(w);
+
+    for tick in ticks {
+        writer.serialize(TheCragTick::try_from(tick.clone())?)?;
+    }
+
+    writer.flush().map_err(csv::Error::from)?;
+
+    Ok(())
+}
+
+/// An entry in a training log
+#[derive(Serialize)]
+struct TrainingEntry<'a> {
+    date: Option<NaiveDate>,
+    grade: Option<&'a str>,
+    style: Option<&'static str>,
+    discipline: Vec<&'static str>,
+    attempts: Option<u32>,
+}
+
+impl<'a> From<&'a OpenTick> for TrainingEntry<'a> {
+    fn from(tick: &'a OpenTick) -> Self {
+        TrainingEntry {
+            date: tick.date,
+            grade: tick.effective_grade(),
+            style: tick.ascent_style.and_then(training_style),
+            discipline: tick
+                .ascent_discipline
+                .as_ref()
+                .or(tick.route_discipline.as_ref())
+                .map(training_disciplines)
+                .unwrap_or_default(),
+            attempts: tick.attempts.or(match tick.ascent_style {
+                Some(AscentStyle::Onsight | AscentStyle::Flash) => Some(1),
+                _ => None,
+            }),
+        }
+    }
+}
+
+fn training_style(style: AscentStyle) -> Option<&'static str> {
+    match style {
+        AscentStyle::Onsight => Some("onsight"),
+        AscentStyle::Flash => Some("flash"),
+        AscentStyle::Redpoint => Some("redpoint"),
+        AscentStyle::Pinkpoint => Some("pinkpoint"),
+        AscentStyle::Greenpoint => Some("greenpoint"),
+        AscentStyle::Send => Some("send"),
+        AscentStyle::Repeat => Some("repeat"),
+        AscentStyle::Lead => Some("lead"),
+        AscentStyle::Hangdog => Some("hangdog"),
+        AscentStyle::TopRope => Some("top_rope"),
+        AscentStyle::Second => Some("second"),
+        AscentStyle::Solo => Some("solo"),
+        AscentStyle::Aid => Some("aid"),
+        AscentStyle::Attempt => Some("attempt"),
+        AscentStyle::Dab => Some("dab"),
+        AscentStyle::Ghost | AscentStyle::Unknown => None,
+    }
+}
+
+fn training_disciplines(discipline: &Discipline) -> Vec<&'static str> {
+    discipline
+        .iter()
+        .filter_map(|kind| match kind {
+            DisciplineKind::Aid => Some("aid"),
+            DisciplineKind::Alpine => Some("alpine"),
+            DisciplineKind::Bouldering => Some("boulder"),
+            DisciplineKind::DeepWaterSolo => Some("deep_water_solo"),
+            DisciplineKind::Ice => Some("ice"),
+            DisciplineKind::Sport => Some("sport"),
+            DisciplineKind::TopRope => Some("top_rope"),
+            DisciplineKind::Trad => Some("trad"),
+            DisciplineKind::Unknown => None,
+        })
+        .collect()
+}
+
+/// Write ticks as a training log in JSON
+///
+/// The log is an array with one object per tick, holding its `date`, `grade`, `style`,
+/// `discipline` and `attempts`.  The ascent grade and discipline are preferred over the route's.
+/// The number of attempts is taken from [`OpenTick::attempts`], or is 1 for onsights and flashes;
+/// otherwise it is `null`, as is any other field that couldn't be determined.
+pub fn to_training_json<W: Write>(ticks: &[OpenTick], w: W) -> Result<(), ExportError> {
+    let entries: Vec<TrainingEntry> = ticks.iter().map(TrainingEntry::from).collect();
+
+    serde_json::to_writer_pretty(w, &entries)?;
+
+    Ok(())
+}
+
+/// Write ticks as YAML, such as to keep a logbook in a file that is easy to edit by hand
+///
+/// The document is a sequence with one mapping per tick, holding the same fields as
+/// [`OpenTick`]'s JSON serialization: dates are `YYYY-MM-DD` strings, disciplines are mappings
+/// of flags, and missing fields are `null`.
+#[cfg(feature = "yaml")]
+pub fn to_yaml<W: Write>(ticks: &[OpenTick], w: W) -> Result<(), ExportError> {
+    serde_norway::to_writer(w, ticks)?;
+
+    Ok(())
+}
+
+/// Write ticks as TOML, such as to keep a logbook in a file that is easy to edit by hand
+///
+/// Each tick is a `[[ticks]]` table holding the same fields as [`OpenTick`]'s JSON serialization,
+/// rendered the same way, except that missing fields are left out since TOML has no null.
+#[cfg(feature = "toml")]
+pub fn to_toml<W: Write>(ticks: &[OpenTick], mut w: W) -> Result<(), ExportError> {
+    #[derive(Serialize)]
+    struct Logbook<'a> {
+        ticks: &'a [OpenTick],
+    }
+
+    w.write_all(toml::to_string(&Logbook { ticks })?.as_bytes())?;
+
+    Ok(())
+}
+
+/// A GeoJSON feature collection
+#[derive(Serialize)]
+#[serde(tag = "type", rename = "FeatureCollection")]
+struct FeatureCollection<'a> {
+    features: Vec<Feature<'a>>,
+}
+
+/// A GeoJSON feature locating one tick
+#[derive(Serialize)]
+#[serde(tag = "type", rename = "Feature")]
+struct Feature<'a> {
+    geometry: Point,
+    properties: FeatureProperties<'a>,
+}
+
+/// A GeoJSON point, as longitude then latitude
+#[derive(Serialize)]
+#[serde(tag = "type", rename = "Point")]
+struct Point {
+    coordinates: [f64; 2],
+}
+
+#[derive(Serialize)]
+struct FeatureProperties<'a> {
+    name: Option<&'a str>,
+    date: Option<NaiveDate>,
+    grade: Option<&'a str>,
+    style: Option<&'static str>,
+}
+
+/// Write ticks with [`OpenTick::coordinates`] as GeoJSON points
+///
+/// The output is a feature collection with one point per tick, holding its route `name`, `date`,
+/// `grade` and `style` as properties.  The ascent grade is preferred over the route's.  Ticks
+/// without coordinates are left out.
+pub fn to_geojson<W: Write>(ticks: &[OpenTick], w: W) -> Result<(), ExportError> {
+    let features = ticks
+        .iter()
+        .filter_map(|tick| {
+            let (latitude, longitude) = tick.coordinates?;
+
+            Some(Feature {
+                geometry: Point {
+                    coordinates: [longitude, latitude],
+                },
+                properties: FeatureProperties {
+                    name: tick.route_name.as_deref(),
+                    date: tick.date,
+                    grade: tick.effective_grade(),
+                    style: tick.ascent_style.and_then(training_style),
+                },
+            })
+        })
+        .collect();
+
+    serde_json::to_writer_pretty(w, &FeatureCollection { features })?;
+
+    Ok(())
+}
+
+/// A plain text summary of the ticks, as shared after a day out
+///
+/// Ticks are grouped by date, in order of first appearance, under a header line with the date,
+/// or `Undated`.  Each tick is then listed on its own line, as by its [`Display`](fmt::Display)
+/// implementation without the date, showing its grade and how it went.  Groups are separated by
+/// a blank line.
+///
+/// # Examples
+/// ```
+/// use open_tick::export::session_summary;
+/// use open_tick::{AscentStyle, OpenTick};
+///
+/// let mut tick = OpenTick::default();
+/// tick.date = chrono::NaiveDate::from_ymd_opt(2023, 6, 1);
+/// tick.route_name = Some("Sautanz".to_string());
+/// tick.ascent_style = Some(AscentStyle::Redpoint);
+///
+/// assert_eq!(session_summary(&[tick]), "2023-06-01\n- Sautanz — redpoint\n");
+/// ```
+pub fn session_summary(ticks: &[OpenTick]) -> String {
+    let mut sessions: Vec<(Option<PartialDate>, Vec<&OpenTick>)> = Vec::new();
+
+    for tick in ticks {
+        let date = tick.date.map(PartialDate::Full).or(tick.partial_date);
+        match sessions.iter_mut().find(|(d, _)| *d == date) {
+            Some((_, session)) => session.push(tick),
+            None => sessions.push((date, vec![tick])),
+        }
+    }
+
+    sessions
+        .into_iter()
+        .map(|(date, session)| {
+            let header = date.map_or_else(|| "Undated".to_string(), |d| d.to_string());
+            let climbs: String = session
+                .into_iter()
+                .map(|tick| {
+                    let undated = OpenTick {
+                        date: None,
+                        partial_date: None,
+                        ..tick.clone()
+                    };
+                    format!("- {undated}\n")
+                })
+                .collect();
+            format!("{header}\n{climbs}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[cfg(any(feature = "yaml", feature = "toml"))]
+    fn sample_tick() -> OpenTick {
+        OpenTick {
+            date: NaiveDate::from_ymd_opt(2023, 6, 1),
+            route_name: Some("The Nose".to_string()),
+            route_location: Some("California > Yosemite > El Capitan".to_string()),
+            route_discipline: Some("trad, aid".parse().unwrap()),
+            route_grade: Some("5.9 C2".to_string()),
+            route_stars: Some(4.5),
+            ascent_style: Some(AscentStyle::Redpoint),
+            comment: Some("Long day: \"worth it\"\nWould go again".to_string()),
+            partners: Some(vec!["Jo Bloggs".to_string()]),
+            pitches: Some(31),
+            coordinates: Some((37.73, -119.64)),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn yaml_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let ticks = [sample_tick()];
+
+        let mut out = vec![];
+        to_yaml(&ticks, &mut out)?;
+        let yaml = String::from_utf8(out)?;
+
+        assert!(yaml.contains("date: 2023-06-01\n"));
+        assert_eq!(
+            serde_norway::from_str::<serde_json::Value>(&yaml)?,
+            serde_json::to_value(&ticks)?
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_tables() -> Result<(), Box<dyn std::error::Error>> {
+        let ticks = [sample_tick(), OpenTick::default()];
+
+        let mut out = vec![];
+        to_toml(&ticks, &mut out)?;
+        let toml = String::from_utf8(out)?;
+
+        assert_eq!(toml.matches("[[ticks]]").count(), 2);
+        assert!(toml.contains("date = \"2023-06-01\""));
+        assert!(toml.contains("[ticks.route_discipline]\naid = true\nalpine = false\n"));
+
+        let parsed: toml::Value = toml.parse()?;
+        assert_eq!(
+            parsed["ticks"][0]["comment"].as_str(),
+            sample_tick().comment.as_deref()
+        );
+        assert!(parsed["ticks"][1].get("date").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn two_tick_session() {
+        let ascent = |name: &str, grade: &str, style| OpenTick {
+            date: NaiveDate::from_ymd_opt(2023, 6, 1),
+            route_name: Some(name.to_string()),
+            route_grade: Some(grade.to_string()),
+            route_discipline: Some("sport".parse().unwrap()),
+            ascent_style: Some(style),
+            ..Default::default()
+        };
+        let ticks = [
+            ascent("Sautanz", "7a", AscentStyle::Redpoint),
+            ascent("Der Riss", "7b", AscentStyle::Attempt),
+        ];
+
+        assert_eq!(
+            session_summary(&ticks),
+            "2023-06-01\n\
+             - Sautanz (7a, sport) — redpoint\n\
+             - Der Riss (7b, sport) — attempt\n"
+        );
+        assert_eq!(
+            session_summary(&[ticks[0].clone(), OpenTick::default()]),
+            "2023-06-01\n- Sautanz (7a, sport) — redpoint\n\nUndated\n- Unnamed route\n"
+        );
+    }
+
+    #[test]
+    fn thecrag_headers() -> Result<(), Box<dyn std::error::Error>> {
+        let ticks = vec![OpenTick {
+            date: NaiveDate::from_ymd_opt(2023, 6, 1),
+            route_name: Some("A Route".to_string()),
+            route_location: Some("Australia > Victoria > Arapiles".to_string()),
+            ascent_style: Some(AscentStyle::Onsight),
+            ..Default::default()
+        }];
+
+        let mut out = vec![];
+        to_thecrag_csv(&ticks, &mut out)?;
+        let out = String::from_utf8(out)?;
+        let mut lines = out.lines();
+
+        assert_eq!(
+            lines.next(),
+            Some(
+                "Route Name,Ascent Label,Ascent ID,Ascent Link,Ascent Type,Route Grade,\
+                 Ascent Grade,Route Gear Style,Ascent Gear Style,Route Height,Ascent Height,\
+                 # Ascents,Route Stars,Route ID,Route Link,Country,Country Link,Crag Name,\
+                 Crag Link,Crag Path,With,Comment,Quality,Ascent Date,Log Date,Shot"
+            )
+        );
+        assert_eq!(
+            lines.next(),
+            Some(
+                "A Route,,,,Onsight,,,,,,,1,,,,Australia,,Arapiles,,\
+                 Australia > Victoria > Arapiles,,,,2023-06-01T00:00:00Z,,"
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn training_json() -> Result<(), Box<dyn std::error::Error>> {
+        let ticks = vec![
+            OpenTick {
+                date: NaiveDate::from_ymd_opt(2023, 6, 1),
+                route_name: Some("A Route".to_string()),
+                route_grade: Some("5.10a".to_string()),
+                route_discipline: Some(Discipline {
+                    trad: true,
+                    ..Default::default()
+                }),
+                ascent_style: Some(AscentStyle::Onsight),
+                ..Default::default()
+            },
+            OpenTick {
+                date: NaiveDate::from_ymd_opt(2023, 6, 2),
+                route_grade: Some("V4".to_string()),
+                ascent_grade: Some("V3".to_string()),
+                route_discipline: Some(Discipline {
+                    bouldering: true,
+                    ..Default::default()
+                }),
+                ascent_style: Some(AscentStyle::Attempt),
+                ..Default::default()
+            },
+            OpenTick::default(),
+        ];
+
+        let mut out = vec![];
+        to_training_json(&ticks, &mut out)?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            include_str!("../tests/fixtures/training.json").trim_end()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn geojson_points() -> Result<(), Box<dyn std::error::Error>> {
+        let ticks = vec![
+            OpenTick {
+                date: NaiveDate::from_ymd_opt(2023, 6, 1),
+                route_name: Some("Syzygy".to_string()),
+                route_grade: Some("23".to_string()),
+                coordinates: Some((-36.74, 141.84)),
+                ascent_style: Some(AscentStyle::Onsight),
+                ..Default::default()
+            },
+            OpenTick {
+                route_name: Some("Somewhere".to_string()),
+                ..Default::default()
+            },
+        ];
+
+        let mut out = vec![];
+        to_geojson(&ticks, &mut out)?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            include_str!("../tests/fixtures/ticks.geojson").trim_end()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn thecrag_needs_route_name() {
+        let result = to_thecrag_csv(&[OpenTick::default()], vec![]);
+
+        assert!(matches!(
+            result,
+            Err(ExportError::Conversion(ConversionError::MissingField(
+                "route_name"
+            )))
+        ));
+    }
+}
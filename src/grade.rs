@@ -0,0 +1,1185 @@
+//! Climbing grades
+//!
+//! Each grading system has its own type, ordered from easiest to hardest.  [`Grade`] holds a grade
+//! in any of them, keeping the original text when no system recognizes it.
+//!
+//! Converting YDS to French is total: every YDS grade has a French equivalent.  The other
+//! direction is partial, since French grades run harder than the hardest YDS grade, so it is a
+//! [`TryFrom`] that fails with [`ConversionError::NoEquivalent`].  Grades in the other systems
+//! only convert to their own system.
+
+use crate::DisciplineKind;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Grades on a fixed scale, from easiest to hardest
+pub trait Scale: Copy + Ord {
+    /// Position of this grade on its scale, counting up from zero for the easiest
+    fn ordinal(self) -> u32;
+    /// Grade at the given position on its scale, if the scale extends that far
+    fn from_ordinal(ordinal: u32) -> Option<Self>;
+}
+
+/// Errors in parsing a grade
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseGradeError {
+    /// The text isn't a grade in the expected system
+    Unrecognized(String),
+    /// A range whose upper bound is easier than its lower bound
+    Inverted(String),
+}
+
+impl fmt::Display for ParseGradeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseGradeError::Unrecognized(s) => write!(f, "unrecognized grade: {s:?}"),
+            ParseGradeError::Inverted(s) => write!(f, "grade range is inverted: {s:?}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseGradeError {}
+
+/// Errors in converting a grade to another system
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConversionError {
+    /// The grade has no published equivalent in the target system
+    NoEquivalent {
+        /// The grade being converted, as written
+        grade: String,
+        /// The system it was being converted to
+        system: GradeSystem,
+    },
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConversionError::NoEquivalent { grade, system } => {
+                let system = match system {
+                    GradeSystem::Yds => "YDS",
+                    GradeSystem::French => "French",
+                    GradeSystem::VScale => "V-scale",
+                    GradeSystem::Font => "Fontainebleau",
+                    GradeSystem::WaterIce => "water ice",
+                    GradeSystem::Mixed => "mixed",
+                    GradeSystem::Aid => "aid",
+                };
+                write!(f, "no {system} equivalent of grade {grade:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+/// Whether a grade was understood, as given by
+/// [`OpenTick::grade_parse_status`](crate::OpenTick::grade_parse_status)
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GradeParseStatus {
+    /// The grade is in a recognized system
+    Parsed(GradeSystem),
+    /// The grade is kept as text, as in [`Grade::Raw`]
+    Unrecognized,
+    /// There is no grade
+    Missing,
+}
+
+/// Preferred grade system for each discipline
+///
+/// Climbers in the US usually grade routes in YDS and those in Europe in French, so analyses
+/// take the preferences to use.  By default routes are graded in YDS, boulders on the V-scale,
+/// ice in water ice grades and aid in aid grades.
+///
+/// # Examples
+/// ```
+/// use open_tick::grade::{GradePreferences, GradeSystem};
+/// use open_tick::DisciplineKind;
+///
+/// let preferences = GradePreferences::default().with(DisciplineKind::Sport, GradeSystem::French);
+/// assert_eq!(preferences.system_for(DisciplineKind::Sport), GradeSystem::French);
+/// assert_eq!(preferences.system_for(DisciplineKind::Trad), GradeSystem::Yds);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GradePreferences {
+    systems: BTreeMap<DisciplineKind, GradeSystem>,
+}
+
+impl GradePreferences {
+    /// Prefer `system` for `kind`
+    pub fn with(mut self, kind: DisciplineKind, system: GradeSystem) -> Self {
+        self.systems.insert(kind, system);
+        self
+    }
+
+    /// The preferred system for `kind`
+    pub fn system_for(&self, kind: DisciplineKind) -> GradeSystem {
+        self.systems.get(&kind).copied().unwrap_or(GradeSystem::Yds)
+    }
+}
+
+impl Default for GradePreferences {
+    fn default() -> Self {
+        GradePreferences {
+            systems: BTreeMap::from([
+                (DisciplineKind::Aid, GradeSystem::Aid),
+                (DisciplineKind::Bouldering, GradeSystem::VScale),
+                (DisciplineKind::Ice, GradeSystem::WaterIce),
+            ]),
+        }
+    }
+}
+
+/// Broad band of difficulty, as given by [`Grade::band`], from easiest to hardest
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GradeBand {
+    Beginner,
+    Intermediate,
+    Advanced,
+    Expert,
+    Elite,
+    /// The grade isn't in a recognized system
+    Unknown,
+}
+
+/// Grading systems
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GradeSystem {
+    /// Yosemite Decimal System, e.g. `5.10a`
+    Yds,
+    /// French sport grades, e.g. `6a+`
+    French,
+    /// Hueco V-scale for boulders, e.g. `V4`
+    VScale,
+    /// Fontainebleau boulder grades, e.g. `6B+`
+    Font,
+    /// Water ice grades, e.g. `WI4`
+    WaterIce,
+    /// Mixed rock and ice grades, e.g. `M6`
+    Mixed,
+    /// Aid grades, e.g. `A2`
+    Aid,
+}
+
+impl GradeSystem {
+    /// Disciplines the system grades
+    ///
+    /// A route graded in a system that doesn't apply to any of its disciplines, such as a boulder
+    /// with a YDS grade, is likely mislabelled.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::grade::GradeSystem;
+    /// use open_tick::DisciplineKind;
+    ///
+    /// assert!(GradeSystem::Yds.applies_to().contains(&DisciplineKind::Trad));
+    /// assert!(!GradeSystem::Yds.applies_to().contains(&DisciplineKind::Bouldering));
+    /// ```
+    pub fn applies_to(&self) -> &'static [DisciplineKind] {
+        match self {
+            GradeSystem::Yds | GradeSystem::French => &[
+                DisciplineKind::Sport,
+                DisciplineKind::TopRope,
+                DisciplineKind::Trad,
+            ],
+            GradeSystem::VScale | GradeSystem::Font => &[DisciplineKind::Bouldering],
+            GradeSystem::WaterIce | GradeSystem::Mixed => &[DisciplineKind::Ice],
+            GradeSystem::Aid => &[DisciplineKind::Aid],
+        }
+    }
+}
+
+const YDS: [&str; 34] = [
+    "5.0", "5.1", "5.2", "5.3", "5.4", "5.5", "5.6", "5.7", "5.8", "5.9", "5.10a", "5.10b",
+    "5.10c", "5.10d", "5.11a", "5.11b", "5.11c", "5.11d", "5.12a", "5.12b", "5.12c", "5.12d",
+    "5.13a", "5.13b", "5.13c", "5.13d", "5.14a", "5.14b", "5.14c", "5.14d", "5.15a", "5.15b",
+    "5.15c", "5.15d",
+];
+
+const FRENCH: [&str; 39] = [
+    "1", "2", "3", "4a", "4a+", "4b", "4b+", "4c", "4c+", "5a", "5a+", "5b", "5b+", "5c", "5c+",
+    "6a", "6a+", "6b", "6b+", "6c", "6c+", "7a", "7a+", "7b", "7b+", "7c", "7c+", "8a", "8a+",
+    "8b", "8b+", "8c", "8c+", "9a", "9a+", "9b", "9b+", "9c", "9c+",
+];
+
+const V_SCALE: [&str; 19] = [
+    "VB", "V0", "V1", "V2", "V3", "V4", "V5", "V6", "V7", "V8", "V9", "V10", "V11", "V12", "V13",
+    "V14", "V15", "V16", "V17",
+];
+
+const FONT: [&str; 26] = [
+    "1", "2", "3", "4", "4+", "5", "5+", "6A", "6A+", "6B", "6B+", "6C", "6C+", "7A", "7A+", "7B",
+    "7B+", "7C", "7C+", "8A", "8A+", "8B", "8B+", "8C", "8C+", "9A",
+];
+
+const WATER_ICE: [&str; 7] = ["WI1", "WI2", "WI3", "WI4", "WI5", "WI6", "WI7"];
+
+const MIXED: [&str; 15] = [
+    "M1", "M2", "M3", "M4", "M5", "M6", "M7", "M8", "M9", "M10", "M11", "M12", "M13", "M14", "M15",
+];
+
+const AID: [&str; 6] = ["A0", "A1", "A2", "A3", "A4", "A5"];
+
+fn position(names: &[&str], s: &str) -> Option<u8> {
+    names.iter().position(|&n| n == s).map(|i| i as u8)
+}
+
+fn name_at(names: &[&str], ordinal: u32) -> Option<u8> {
+    (ordinal < names.len() as u32).then_some(ordinal as u8)
+}
+
+/// A grade in the Yosemite Decimal System
+///
+/// Grades from 5.10 up are split by letter.  When parsing, a letterless grade such as `5.10` is
+/// taken as `5.10b`, `5.10-` as `5.10a` and `5.10+` as `5.10c`.  Below 5.10, `+` and `-` are
+/// dropped.
+///
+/// # Examples
+/// ```
+/// use open_tick::grade::Yds;
+///
+/// let grade: Yds = "5.10".parse().unwrap();
+/// assert_eq!(grade.to_string(), "5.10b");
+/// assert!(grade < "5.11-".parse().unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Yds(u8);
+
+impl FromStr for Yds {
+    type Err = ParseGradeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if let Some(i) = position(&YDS, s) {
+            return Ok(Yds(i));
+        }
+
+        let (base, letter) = if let Some(base) = s.strip_suffix('-') {
+            (base, "a")
+        } else if let Some(base) = s.strip_suffix('+') {
+            (base, "c")
+        } else {
+            (s, "b")
+        };
+
+        position(&YDS, base)
+            .filter(|&i| i < 10)
+            .or_else(|| position(&YDS, &format!("{base}{letter}")))
+            .map(Yds)
+            .ok_or_else(|| ParseGradeError::Unrecognized(s.to_string()))
+    }
+}
+
+impl fmt::Display for Yds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(YDS[self.0 as usize])
+    }
+}
+
+impl Scale for Yds {
+    fn ordinal(self) -> u32 {
+        self.0.into()
+    }
+
+    fn from_ordinal(ordinal: u32) -> Option<Self> {
+        name_at(&YDS, ordinal).map(Yds)
+    }
+}
+
+/// A French sport grade
+///
+/// # Examples
+/// ```
+/// use open_tick::grade::French;
+///
+/// let grade: French = "6a+".parse().unwrap();
+/// assert!(grade < "6b".parse().unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct French(u8);
+
+impl FromStr for French {
+    type Err = ParseGradeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        position(&FRENCH, s)
+            .map(French)
+            .ok_or_else(|| ParseGradeError::Unrecognized(s.to_string()))
+    }
+}
+
+impl fmt::Display for French {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(FRENCH[self.0 as usize])
+    }
+}
+
+impl Scale for French {
+    fn ordinal(self) -> u32 {
+        self.0.into()
+    }
+
+    fn from_ordinal(ordinal: u32) -> Option<Self> {
+        name_at(&FRENCH, ordinal).map(French)
+    }
+}
+
+/// A boulder grade on the Hueco V-scale
+///
+/// When parsing, `+` and `-` modifiers are dropped.
+///
+/// # Examples
+/// ```
+/// use open_tick::grade::VScale;
+///
+/// let grade: VScale = "V4+".parse().unwrap();
+/// assert_eq!(grade.to_string(), "V4");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VScale(u8);
+
+impl FromStr for VScale {
+    type Err = ParseGradeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let base = s.strip_suffix(['+', '-']).unwrap_or(s);
+
+        position(&V_SCALE, base)
+            .map(VScale)
+            .ok_or_else(|| ParseGradeError::Unrecognized(s.to_string()))
+    }
+}
+
+impl fmt::Display for VScale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(V_SCALE[self.0 as usize])
+    }
+}
+
+impl Scale for VScale {
+    fn ordinal(self) -> u32 {
+        self.0.into()
+    }
+
+    fn from_ordinal(ordinal: u32) -> Option<Self> {
+        name_at(&V_SCALE, ordinal).map(VScale)
+    }
+}
+
+/// A grade type backed by a table of names, with `FromStr`, `Display` and [`Scale`]
+///
+/// With `strip_modifiers`, `+` and `-` are dropped when parsing.
+macro_rules! table_scale {
+    ($(#[$attr:meta])* $name:ident, $table:ident, strip_modifiers: $strip:literal) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(u8);
+
+        impl FromStr for $name {
+            type Err = ParseGradeError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let s = s.trim();
+                let base = if $strip {
+                    s.strip_suffix(['+', '-']).unwrap_or(s)
+                } else {
+                    s
+                };
+
+                position(&$table, base)
+                    .map($name)
+                    .ok_or_else(|| ParseGradeError::Unrecognized(s.to_string()))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str($table[self.0 as usize])
+            }
+        }
+
+        impl Scale for $name {
+            fn ordinal(self) -> u32 {
+                self.0.into()
+            }
+
+            fn from_ordinal(ordinal: u32) -> Option<Self> {
+                name_at(&$table, ordinal).map($name)
+            }
+        }
+
+        impl From<$name> for Grade {
+            fn from(value: $name) -> Self {
+                Grade::$name(value)
+            }
+        }
+    };
+}
+
+table_scale!(
+    /// A Fontainebleau boulder grade
+    ///
+    /// Letters are upper case, as in `6B+`, which keeps them apart from French sport grades.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::grade::Font;
+    ///
+    /// let grade: Font = "6B+".parse().unwrap();
+    /// assert!(grade < "6C".parse().unwrap());
+    /// ```
+    Font,
+    FONT,
+    strip_modifiers: false
+);
+
+table_scale!(
+    /// A water ice grade
+    ///
+    /// When parsing, `+` and `-` modifiers are dropped.
+    WaterIce,
+    WATER_ICE,
+    strip_modifiers: true
+);
+
+table_scale!(
+    /// A mixed rock and ice grade
+    ///
+    /// When parsing, `+` and `-` modifiers are dropped.
+    Mixed,
+    MIXED,
+    strip_modifiers: true
+);
+
+table_scale!(
+    /// An aid grade
+    ///
+    /// When parsing, `+` and `-` modifiers are dropped.
+    Aid,
+    AID,
+    strip_modifiers: true
+);
+
+/// A span of grades, for routes graded between two, e.g. `5.10a/b`
+///
+/// When parsing, the bounds may be separated by `/` or `-`, and the upper bound may leave out the
+/// start it shares with the lower bound, as in `5.10a/b` or `V4-6`.  The range is displayed in the
+/// same short form, always separated by `/`.
+///
+/// # Examples
+/// ```
+/// use open_tick::grade::{Range, Yds};
+///
+/// let range: Range<Yds> = "5.10a/b".parse().unwrap();
+/// assert_eq!(range.low, "5.10a".parse().unwrap());
+/// assert_eq!(range.high, "5.10b".parse().unwrap());
+/// assert_eq!(range.to_string(), "5.10a/b");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Range<G> {
+    /// Easiest grade in the range
+    pub low: G,
+    /// Hardest grade in the range
+    pub high: G,
+}
+
+impl<G: Scale> Range<G> {
+    /// Grade halfway between the bounds, rounding down
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::grade::{Range, VScale};
+    ///
+    /// let range: Range<VScale> = "V4-7".parse().unwrap();
+    /// assert_eq!(range.midpoint().to_string(), "V5");
+    /// ```
+    pub fn midpoint(&self) -> G {
+        let ordinal = (self.low.ordinal() + self.high.ordinal()) / 2;
+        G::from_ordinal(ordinal).unwrap_or(self.low)
+    }
+}
+
+impl<G> FromStr for Range<G>
+where
+    G: Scale + FromStr<Err = ParseGradeError>,
+{
+    type Err = ParseGradeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let unrecognized = || ParseGradeError::Unrecognized(s.to_string());
+
+        let (low, high) = ['/', '-']
+            .into_iter()
+            .filter_map(|sep| s.split_once(sep))
+            .map(|(low, high)| (low.trim(), high.trim()))
+            .find(|(low, high)| !low.is_empty() && !high.is_empty())
+            .ok_or_else(unrecognized)?;
+
+        let low_grade: G = low.parse()?;
+        // Complete an abbreviated upper bound with as little of the lower bound as is needed, so
+        // that `5.1/2` is 5.1 to 5.2, not to 5.12
+        let high_grade: G = (0..=low.len())
+            .filter(|&i| low.is_char_boundary(i))
+            .find_map(|i| format!("{}{high}", &low[..i]).parse().ok())
+            .ok_or_else(unrecognized)?;
+
+        if high_grade < low_grade {
+            return Err(ParseGradeError::Inverted(s.to_string()));
+        }
+
+        Ok(Range {
+            low: low_grade,
+            high: high_grade,
+        })
+    }
+}
+
+impl<G: fmt::Display> fmt::Display for Range<G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let low = self.low.to_string();
+        let high = self.high.to_string();
+
+        let mut shared = low
+            .char_indices()
+            .zip(high.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(low.len().min(high.len()), |((i, _), _)| i);
+        // Don't split a number, or `V1/10` would read as `V1/0`
+        while low[..shared].ends_with(|c: char| c.is_ascii_digit())
+            && high[shared..].starts_with(|c: char| c.is_ascii_digit())
+        {
+            shared -= 1;
+        }
+        if shared == high.len() {
+            shared = 0;
+        }
+
+        write!(f, "{low}/{}", &high[shared..])
+    }
+}
+
+/// A grade in any system
+///
+/// # Examples
+/// ```
+/// use open_tick::grade::{Grade, GradeSystem};
+///
+/// assert_eq!(Grade::parse("6a+/6b").system(), Some(GradeSystem::French));
+/// assert_eq!(Grade::parse("E5 6b"), Grade::Raw("E5 6b".to_string()));
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Grade {
+    /// A grade in the Yosemite Decimal System
+    Yds(Yds),
+    /// A French sport grade
+    French(French),
+    /// A boulder grade on the V-scale
+    VScale(VScale),
+    /// A Fontainebleau boulder grade
+    Font(Font),
+    /// A water ice grade
+    WaterIce(WaterIce),
+    /// A mixed rock and ice grade
+    Mixed(Mixed),
+    /// An aid grade
+    Aid(Aid),
+    /// A span between two grades in the same system
+    Range(Box<Range<Grade>>),
+    /// A grade that isn't recognized in any system, as written
+    Raw(String),
+}
+
+impl Grade {
+    /// Parse a grade in any system, keeping the text as [`Grade::Raw`] if none recognizes it
+    ///
+    /// theCrag sometimes prefixes a grade with the code of its system, such as `{FR}7a` or
+    /// `{YDS}5.11`.  The grade is then parsed in that system only.  Prefixes for systems this
+    /// crate doesn't know, such as `{UIAA}`, leave the grade raw.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::grade::{Grade, GradeSystem};
+    ///
+    /// assert_eq!(Grade::parse("{FR}7a").system(), Some(GradeSystem::French));
+    /// assert_eq!(Grade::parse("{UIAA}VII"), Grade::Raw("{UIAA}VII".to_string()));
+    /// ```
+    pub fn parse(s: &str) -> Grade {
+        fn range<G: Scale + FromStr<Err = ParseGradeError> + Into<Grade>>(
+            s: &str,
+        ) -> Option<Grade> {
+            let range: Range<G> = s.parse().ok()?;
+            Some(Grade::Range(Box::new(Range {
+                low: range.low.into(),
+                high: range.high.into(),
+            })))
+        }
+        fn single_or_range<G: Scale + FromStr<Err = ParseGradeError> + Into<Grade>>(
+            s: &str,
+        ) -> Option<Grade> {
+            s.parse::<G>()
+                .ok()
+                .map(Into::into)
+                .or_else(|| range::<G>(s))
+        }
+
+        if let Some((code, grade)) = s
+            .trim_start()
+            .strip_prefix('{')
+            .and_then(|s| s.split_once('}'))
+        {
+            let grade = grade.trim();
+            let parsed = match code.trim().to_uppercase().as_str() {
+                "YDS" => single_or_range::<Yds>(grade),
+                "FR" => single_or_range::<French>(grade),
+                "V" => single_or_range::<VScale>(grade),
+                "FB" => single_or_range::<Font>(grade),
+                "WI" => single_or_range::<WaterIce>(grade),
+                "M" => single_or_range::<Mixed>(grade),
+                _ => None,
+            };
+            return parsed.unwrap_or_else(|| Grade::Raw(s.to_string()));
+        }
+
+        s.parse()
+            .map(Grade::Yds)
+            .or_else(|_| s.parse().map(Grade::VScale))
+            .or_else(|_| s.parse().map(Grade::French))
+            .or_else(|_| s.parse().map(Grade::Font))
+            .or_else(|_| s.parse().map(Grade::WaterIce))
+            .or_else(|_| s.parse().map(Grade::Mixed))
+            .or_else(|_| s.parse().map(Grade::Aid))
+            .ok()
+            .or_else(|| range::<Yds>(s))
+            .or_else(|| range::<VScale>(s))
+            .or_else(|| range::<French>(s))
+            .or_else(|| range::<Font>(s))
+            .or_else(|| range::<WaterIce>(s))
+            .or_else(|| range::<Mixed>(s))
+            .or_else(|| range::<Aid>(s))
+            .unwrap_or_else(|| Grade::Raw(s.to_string()))
+    }
+
+    /// The equivalent grade in another system
+    ///
+    /// YDS and French grades convert to each other, as far as the scales overlap.  Other systems
+    /// only "convert" to themselves.
+    ///
+    /// # Errors
+    /// [`ConversionError::NoEquivalent`] if the grade has no equivalent in `system`.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::grade::{Grade, GradeSystem};
+    ///
+    /// let french = Grade::parse("5.11d").to_system(GradeSystem::French);
+    /// assert_eq!(french, Ok(Grade::parse("7a")));
+    /// assert!(Grade::parse("V3").to_system(GradeSystem::Yds).is_err());
+    /// ```
+    pub fn to_system(&self, system: GradeSystem) -> Result<Grade, ConversionError> {
+        match (self, system) {
+            (grade, system) if grade.system() == Some(system) => Ok(grade.clone()),
+            (Grade::Yds(g), GradeSystem::French) => Ok(Grade::French((*g).into())),
+            (Grade::French(g), GradeSystem::Yds) => Ok(Grade::Yds((*g).try_into()?)),
+            (Grade::Range(range), system) => Ok(Grade::Range(Box::new(Range {
+                low: range.low.to_system(system)?,
+                high: range.high.to_system(system)?,
+            }))),
+            (grade, system) => Err(ConversionError::NoEquivalent {
+                grade: grade.to_string(),
+                system,
+            }),
+        }
+    }
+
+    /// System the grade is in, unless it is unrecognized
+    pub fn system(&self) -> Option<GradeSystem> {
+        match self {
+            Grade::Yds(_) => Some(GradeSystem::Yds),
+            Grade::French(_) => Some(GradeSystem::French),
+            Grade::VScale(_) => Some(GradeSystem::VScale),
+            Grade::Font(_) => Some(GradeSystem::Font),
+            Grade::WaterIce(_) => Some(GradeSystem::WaterIce),
+            Grade::Mixed(_) => Some(GradeSystem::Mixed),
+            Grade::Aid(_) => Some(GradeSystem::Aid),
+            Grade::Range(range) => range.low.system(),
+            Grade::Raw(_) => None,
+        }
+    }
+
+    /// Rank of the grade within its system, counting up from zero for the easiest
+    ///
+    /// Each grade harder is one more, so ranks can be used to sort, bucket and weigh grades of a
+    /// system:
+    /// - YDS: `5.0` is 0, `5.9` is 9 and `5.10a` is 10, with each letter grade a step above.
+    /// - French: `1` is 0, `4a` is 3 and `6a` is 15, with each `+` a step.
+    /// - V-scale: `VB` is 0 and `V0` is 1.
+    /// - Fontainebleau: `1` is 0 and `6A` is 7, with each `+` a step.
+    /// - Water ice, mixed and aid: `WI1`, `M1` and `A0` are 0.
+    ///
+    /// A range takes the rank of its easier bound.  `None` for [`Grade::Raw`].  Ranks in
+    /// different systems don't compare; see [`Grade::to_system`].
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::grade::Grade;
+    ///
+    /// assert_eq!(Grade::parse("5.10a").ordinal(), Some(10));
+    /// assert_eq!(Grade::parse("V4/5").ordinal(), Some(5));
+    /// assert_eq!(Grade::parse("HVS").ordinal(), None);
+    /// ```
+    pub fn ordinal(&self) -> Option<u32> {
+        self.bounds().map(|(_, low, _)| low)
+    }
+
+    /// The broad band the grade falls in, for summaries that don't need every grade
+    ///
+    /// Bands are set per system, lining up roughly across systems for the same discipline:
+    ///
+    /// | Band         | YDS           | French    | V-scale | Font     | Water ice | Mixed  | Aid |
+    /// |--------------|---------------|-----------|---------|----------|-----------|--------|-----|
+    /// | Beginner     | up to 5.7     | up to 5a+ | VB–V1   | up to 5  | WI1–WI2   | M1–M3  | A0  |
+    /// | Intermediate | 5.8–5.10d     | 5b–6b+    | V2–V4   | 5+–6B+   | WI3       | M4–M5  | A1  |
+    /// | Advanced     | 5.11a–5.12d   | 6c–7c+    | V5–V7   | 6C–7A+   | WI4       | M6–M7  | A2  |
+    /// | Expert       | 5.13a–5.13d   | 8a–8b+    | V8–V10  | 7B–7C+   | WI5       | M8–M10 | A3  |
+    /// | Elite        | 5.14a and up  | 8c and up | V11 up  | 8A up    | WI6 up    | M11 up | A4+ |
+    ///
+    /// A range falls in the band of its easier bound, as for [`Grade::ordinal`].
+    /// [`GradeBand::Unknown`] for [`Grade::Raw`].
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::grade::{Grade, GradeBand};
+    ///
+    /// assert_eq!(Grade::parse("5.10a").band(), GradeBand::Intermediate);
+    /// assert_eq!(Grade::parse("V9").band(), GradeBand::Expert);
+    /// assert_eq!(Grade::parse("HVS").band(), GradeBand::Unknown);
+    /// ```
+    pub fn band(&self) -> GradeBand {
+        let Some((system, ordinal, _)) = self.bounds() else {
+            return GradeBand::Unknown;
+        };
+
+        // Ordinals at which each band from intermediate up starts
+        let starts = match system {
+            GradeSystem::Yds => [8, 14, 22, 26],
+            GradeSystem::French => [11, 19, 27, 31],
+            GradeSystem::VScale => [3, 6, 9, 12],
+            GradeSystem::Font => [6, 11, 15, 19],
+            GradeSystem::WaterIce => [2, 3, 4, 5],
+            GradeSystem::Mixed => [3, 5, 7, 10],
+            GradeSystem::Aid => [1, 2, 3, 4],
+        };
+
+        match starts.iter().filter(|&&start| ordinal >= start).count() {
+            0 => GradeBand::Beginner,
+            1 => GradeBand::Intermediate,
+            2 => GradeBand::Advanced,
+            3 => GradeBand::Expert,
+            _ => GradeBand::Elite,
+        }
+    }
+
+    /// Compare grades in the same system, `None` if they aren't
+    ///
+    /// Ranges compare by their lower bound, then their upper bound, so `5.10a/b` is between
+    /// `5.10a` and `5.10b`.
+    pub(crate) fn cmp_same_system(&self, other: &Grade) -> Option<std::cmp::Ordering> {
+        let (system, low, high) = self.bounds()?;
+        let (other_system, other_low, other_high) = other.bounds()?;
+
+        (system == other_system).then(|| (low, high).cmp(&(other_low, other_high)))
+    }
+
+    /// System and the ordinals of the easiest and hardest grade covered
+    pub(crate) fn bounds(&self) -> Option<(GradeSystem, u32, u32)> {
+        let ordinal = match self {
+            Grade::Yds(g) => g.ordinal(),
+            Grade::French(g) => g.ordinal(),
+            Grade::VScale(g) => g.ordinal(),
+            Grade::Font(g) => g.ordinal(),
+            Grade::WaterIce(g) => g.ordinal(),
+            Grade::Mixed(g) => g.ordinal(),
+            Grade::Aid(g) => g.ordinal(),
+            Grade::Range(range) => {
+                let (system, low, _) = range.low.bounds()?;
+                let (_, _, high) = range.high.bounds()?;
+                return Some((system, low, high));
+            }
+            Grade::Raw(_) => return None,
+        };
+
+        Some((self.system()?, ordinal, ordinal))
+    }
+}
+
+impl fmt::Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Grade::Yds(g) => g.fmt(f),
+            Grade::French(g) => g.fmt(f),
+            Grade::VScale(g) => g.fmt(f),
+            Grade::Font(g) => g.fmt(f),
+            Grade::WaterIce(g) => g.fmt(f),
+            Grade::Mixed(g) => g.fmt(f),
+            Grade::Aid(g) => g.fmt(f),
+            Grade::Range(range) => range.fmt(f),
+            Grade::Raw(s) => f.write_str(s),
+        }
+    }
+}
+
+/// Grades compare when they are in the same system, or one converts to the other's
+///
+/// YDS and French grades are compared in French, since every YDS grade has a French equivalent.
+/// Ranges compare as by their bounds, so `5.10a/b` is between `5.10a` and `5.10b`.
+///
+/// The comparison is `None`:
+/// - if either grade is [`Grade::Raw`],
+/// - between systems with no conversion, such as French and the V-scale,
+/// - for distinct grades that come out equally hard, such as `5.10a` and `6a`, so that ordering
+///   agrees with `==`.
+///
+/// # Examples
+/// ```
+/// use open_tick::grade::Grade;
+///
+/// assert!(Grade::parse("6a+") < Grade::parse("6b"));
+/// assert!(Grade::parse("5.12a") > Grade::parse("7a"));
+/// assert_eq!(Grade::parse("6a").partial_cmp(&Grade::parse("V3")), None);
+/// ```
+impl PartialOrd for Grade {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        if self == other {
+            return Some(std::cmp::Ordering::Equal);
+        }
+
+        let ordering = match (self.system()?, other.system()?) {
+            (system, other_system) if system == other_system => self.cmp_same_system(other)?,
+            (GradeSystem::French, _) => {
+                self.cmp_same_system(&other.to_system(GradeSystem::French).ok()?)?
+            }
+            (_, GradeSystem::French) => self
+                .to_system(GradeSystem::French)
+                .ok()?
+                .cmp_same_system(other)?,
+            _ => return None,
+        };
+
+        ordering.is_ne().then_some(ordering)
+    }
+}
+
+impl From<Yds> for Grade {
+    fn from(value: Yds) -> Self {
+        Grade::Yds(value)
+    }
+}
+
+impl From<French> for Grade {
+    fn from(value: French) -> Self {
+        Grade::French(value)
+    }
+}
+
+impl From<VScale> for Grade {
+    fn from(value: VScale) -> Self {
+        Grade::VScale(value)
+    }
+}
+
+/// French equivalent of each YDS grade, by ordinal
+const YDS_TO_FRENCH: [u8; 34] = [
+    0, 0, 1, 1, 2, 3, 5, 7, 11, 13, 15, 16, 17, 18, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29,
+    30, 31, 32, 33, 34, 35, 36, 37,
+];
+
+/// Nearest French grade, per the usual conversion charts
+impl From<Yds> for French {
+    fn from(value: Yds) -> Self {
+        French(YDS_TO_FRENCH[value.0 as usize])
+    }
+}
+
+/// Easiest YDS grade whose French equivalent is at least as hard
+///
+/// French grades harder than the French equivalent of 5.15d have no YDS equivalent.
+impl TryFrom<French> for Yds {
+    type Error = ConversionError;
+
+    fn try_from(value: French) -> Result<Self, Self::Error> {
+        YDS_TO_FRENCH
+            .iter()
+            .position(|&f| f >= value.0)
+            .map(|i| Yds(i as u8))
+            .ok_or_else(|| ConversionError::NoEquivalent {
+                grade: value.to_string(),
+                system: GradeSystem::Yds,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn yds(s: &str) -> Yds {
+        s.parse().expect("valid YDS grade")
+    }
+
+    #[test]
+    fn yds_modifiers() {
+        assert_eq!(yds("5.10-"), yds("5.10a"));
+        assert_eq!(yds("5.10"), yds("5.10b"));
+        assert_eq!(yds("5.10+"), yds("5.10c"));
+        assert_eq!(yds("5.9+"), yds("5.9"));
+        assert!("5.16a".parse::<Yds>().is_err());
+    }
+
+    #[test]
+    fn yds_range() {
+        let range: Range<Yds> = "5.10a/b".parse().expect("valid range");
+
+        assert_eq!(
+            range,
+            Range {
+                low: yds("5.10a"),
+                high: yds("5.10b"),
+            }
+        );
+        assert_eq!(range.to_string(), "5.10a/b");
+        assert_eq!(
+            Grade::parse("5.10a/b"),
+            Grade::Range(Box::new(Range {
+                low: Grade::Yds(yds("5.10a")),
+                high: Grade::Yds(yds("5.10b")),
+            }))
+        );
+    }
+
+    #[test]
+    fn range_separators() -> Result<(), ParseGradeError> {
+        let french: Range<French> = "6a+/6b".parse()?;
+        let v: Range<VScale> = "V4-6".parse()?;
+        let wide: Range<VScale> = "V1-10".parse()?;
+
+        assert_eq!(french.to_string(), "6a+/b");
+        assert_eq!(v.to_string(), "V4/6");
+        assert_eq!(wide.to_string(), "V1/10");
+        assert_eq!(wide, wide.to_string().parse()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn range_inverted() {
+        assert_eq!(
+            "6b/6a".parse::<Range<French>>(),
+            Err(ParseGradeError::Inverted("6b/6a".to_string()))
+        );
+    }
+
+    #[test]
+    fn range_midpoint() {
+        let range: Range<Yds> = "5.10a/d".parse().expect("valid range");
+
+        assert_eq!(range.midpoint(), yds("5.10b"));
+    }
+
+    #[test]
+    fn single_grade_not_range() {
+        assert_eq!(Grade::parse("5.10-"), Grade::Yds(yds("5.10a")));
+        assert_eq!(Grade::parse("V0-"), Grade::VScale("V0".parse().unwrap()));
+    }
+
+    #[test]
+    fn compare_within_system() {
+        use std::cmp::Ordering;
+
+        let range = Grade::parse("5.10a/b");
+
+        assert_eq!(
+            range.cmp_same_system(&Grade::parse("5.10a")),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            range.cmp_same_system(&Grade::parse("5.10b")),
+            Some(Ordering::Less)
+        );
+        assert_eq!(range.cmp_same_system(&Grade::parse("V1")), None);
+    }
+
+    #[test]
+    fn compare_across_systems() {
+        use std::cmp::Ordering;
+
+        let grade = Grade::parse;
+
+        assert_eq!(grade("6a+").partial_cmp(&grade("6b")), Some(Ordering::Less));
+        assert_eq!(grade("6b").partial_cmp(&grade("6b")), Some(Ordering::Equal));
+        assert_eq!(
+            grade("5.12a").partial_cmp(&grade("7a")),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            grade("7a").partial_cmp(&grade("5.12a")),
+            Some(Ordering::Less)
+        );
+        assert_eq!(grade("6a").partial_cmp(&grade("5.10a")), None);
+        assert_eq!(grade("6a").partial_cmp(&grade("V3")), None);
+        assert_eq!(grade("E5 6b").partial_cmp(&grade("6b")), None);
+    }
+
+    #[test]
+    fn other_systems() {
+        assert_eq!(Grade::parse("6B+").system(), Some(GradeSystem::Font));
+        assert_eq!(Grade::parse("6b+").system(), Some(GradeSystem::French));
+        assert_eq!(Grade::parse("WI4+").to_string(), "WI4");
+        assert_eq!(Grade::parse("M7").system(), Some(GradeSystem::Mixed));
+        assert_eq!(Grade::parse("A2/3").to_string(), "A2/3");
+    }
+
+    #[test]
+    fn consecutive_ordinals() {
+        fn ordinals<G: Scale + FromStr + Into<Grade>>(names: &[&str]) -> Vec<Option<u32>> {
+            names
+                .iter()
+                .map(|n| n.parse::<G>().ok().and_then(|g| g.into().ordinal()))
+                .collect()
+        }
+        let expected = |names: &[&str]| (0..names.len() as u32).map(Some).collect::<Vec<_>>();
+
+        assert_eq!(ordinals::<Yds>(&YDS), expected(&YDS));
+        assert_eq!(ordinals::<French>(&FRENCH), expected(&FRENCH));
+        assert_eq!(ordinals::<VScale>(&V_SCALE), expected(&V_SCALE));
+        assert_eq!(ordinals::<Font>(&FONT), expected(&FONT));
+        assert_eq!(ordinals::<WaterIce>(&WATER_ICE), expected(&WATER_ICE));
+        assert_eq!(ordinals::<Mixed>(&MIXED), expected(&MIXED));
+        assert_eq!(ordinals::<Aid>(&AID), expected(&AID));
+        assert_eq!(Grade::parse("6a/6a+").ordinal(), Some(15));
+    }
+
+    #[test]
+    fn thecrag_system_prefix() {
+        assert_eq!(
+            Grade::parse("{FR}7a"),
+            Grade::French("7a".parse().expect("valid French grade"))
+        );
+        assert_eq!(Grade::parse("{YDS} 5.11"), Grade::Yds(yds("5.11")));
+        assert_eq!(
+            Grade::parse("{FB}7A"),
+            Grade::Font("7A".parse().expect("valid Font grade"))
+        );
+        assert_eq!(
+            Grade::parse("{FR}6a/6a+").system(),
+            Some(GradeSystem::French)
+        );
+        // Parsed in the named system only, so a YDS grade labelled French stays raw
+        assert_eq!(Grade::parse("{FR}5.11"), Grade::Raw("{FR}5.11".to_string()));
+        assert_eq!(Grade::parse("{AU}24"), Grade::Raw("{AU}24".to_string()));
+    }
+
+    #[test]
+    fn grade_bands() {
+        let bands = |grades: &[&str]| {
+            grades
+                .iter()
+                .map(|g| Grade::parse(g).band())
+                .collect::<Vec<_>>()
+        };
+        use GradeBand::*;
+
+        assert_eq!(
+            bands(&["5.7", "5.8", "5.10d", "5.11a", "5.12d", "5.13a", "5.14a", "5.15d"]),
+            [
+                Beginner,
+                Intermediate,
+                Intermediate,
+                Advanced,
+                Advanced,
+                Expert,
+                Elite,
+                Elite
+            ]
+        );
+        assert_eq!(
+            bands(&["5a+", "5b", "6c", "7c+", "8a", "8c"]),
+            [Beginner, Intermediate, Advanced, Advanced, Expert, Elite]
+        );
+        assert_eq!(
+            bands(&["VB", "V2", "V7", "V8", "V11"]),
+            [Beginner, Intermediate, Advanced, Expert, Elite]
+        );
+        assert_eq!(
+            bands(&["WI2", "WI3", "M6", "A4", "5.10d/5.11a", "E5 6b"]),
+            [
+                Beginner,
+                Intermediate,
+                Advanced,
+                Elite,
+                Intermediate,
+                Unknown
+            ]
+        );
+    }
+
+    #[test]
+    fn system_applicability() {
+        use DisciplineKind::*;
+
+        let cases = [
+            (GradeSystem::Yds, &[Sport, TopRope, Trad][..]),
+            (GradeSystem::French, &[Sport, TopRope, Trad]),
+            (GradeSystem::VScale, &[Bouldering]),
+            (GradeSystem::Font, &[Bouldering]),
+            (GradeSystem::WaterIce, &[Ice]),
+            (GradeSystem::Mixed, &[Ice]),
+            (GradeSystem::Aid, &[Aid]),
+        ];
+
+        for (system, disciplines) in cases {
+            assert_eq!(system.applies_to(), disciplines, "{system:?}");
+        }
+    }
+
+    #[test]
+    fn yds_french_conversion() {
+        let french = |s: &str| -> French { s.parse().expect("valid French grade") };
+
+        assert_eq!(French::from(yds("5.10a")), french("6a"));
+        assert_eq!(French::from(yds("5.12a")), french("7a+"));
+        assert_eq!(Yds::try_from(french("6b+")), Ok(yds("5.10d")));
+        assert_eq!(Yds::try_from(french("9c")), Ok(yds("5.15d")));
+    }
+
+    #[test]
+    fn conversion_without_equivalent() {
+        assert_eq!(
+            Yds::try_from("9c+".parse::<French>().expect("valid French grade")),
+            Err(ConversionError::NoEquivalent {
+                grade: "9c+".to_string(),
+                system: GradeSystem::Yds,
+            })
+        );
+        assert_eq!(
+            Grade::parse("V3").to_system(GradeSystem::Yds),
+            Err(ConversionError::NoEquivalent {
+                grade: "V3".to_string(),
+                system: GradeSystem::Yds,
+            })
+        );
+        assert!(Grade::parse("8b/9c+").to_system(GradeSystem::Yds).is_err());
+    }
+}
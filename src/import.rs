@@ -0,0 +1,717 @@
+use crate::de::Unrecognized;
+use crate::mountain_project::MountainProjectApiTick;
+use crate::{
+    ConversionError, IntoOpenTick, MountainProjectTick, OpenTick, Source, TheCragTick,
+    VerticalLifeTick,
+};
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read};
+
+/// Errors that can occur while importing ticks from an export
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum ImportError {
+    /// The export could not be read, or a row could not be deserialized
+    Csv(csv::Error),
+    /// A JSON export could not be deserialized
+    Json(serde_json::Error),
+    /// An export could not be read from its file or reader
+    Io(io::Error),
+    /// A row was read, but could not be converted to an [`OpenTick`]
+    Conversion(ConversionError),
+    /// A row has a value not known to this crate, and the import is strict
+    Unrecognized(String),
+    /// The export's columns don't match those of any supported platform
+    UnknownSource,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImportError::Csv(e) => write!(f, "could not read tick: {e}"),
+            ImportError::Json(e) => write!(f, "could not read ticks: {e}"),
+            ImportError::Io(e) => write!(f, "could not read export: {e}"),
+            ImportError::Conversion(e) => write!(f, "could not convert tick: {e}"),
+            ImportError::Unrecognized(value) => write!(f, "unrecognized value: {value:?}"),
+            ImportError::UnknownSource => {
+                f.write_str("could not tell which platform exported ticks")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ImportError::Csv(e) => Some(e),
+            ImportError::Json(e) => Some(e),
+            ImportError::Io(e) => Some(e),
+            ImportError::Conversion(e) => Some(e),
+            ImportError::Unrecognized(_) | ImportError::UnknownSource => None,
+        }
+    }
+}
+
+impl From<csv::Error> for ImportError {
+    fn from(value: csv::Error) -> Self {
+        ImportError::Csv(value)
+    }
+}
+
+impl From<io::Error> for ImportError {
+    fn from(value: io::Error) -> Self {
+        ImportError::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for ImportError {
+    fn from(value: serde_json::Error) -> Self {
+        ImportError::Json(value)
+    }
+}
+
+impl From<ConversionError> for ImportError {
+    fn from(value: ConversionError) -> Self {
+        ImportError::Conversion(value)
+    }
+}
+
+/// Options controlling how exports are imported
+///
+/// # Examples
+/// ```
+/// use open_tick::import::ImportOptions;
+///
+/// let options = ImportOptions::default().strict(true).delimiter(b';');
+/// assert!(options.strict);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    /// Reject rows with values not known to this crate, such as an ascent type added by the
+    /// platform since this crate was released
+    ///
+    /// Otherwise, such values are kept in the `Other` variant of the relevant enum.
+    pub strict: bool,
+    /// Byte separating fields
+    ///
+    /// If `None`, the default, `;` is used if the header line has more of them than `,`, as in
+    /// spreadsheets saved in some European locales, and `,` otherwise.
+    pub delimiter: Option<u8>,
+    /// Byte quoting fields, `"` by default
+    ///
+    /// If `None`, quotes are read as part of the field.
+    pub quote: Option<u8>,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        ImportOptions {
+            strict: false,
+            delimiter: None,
+            quote: Some(b'"'),
+        }
+    }
+}
+
+impl ImportOptions {
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = Some(delimiter);
+        self
+    }
+
+    pub fn quote(mut self, quote: Option<u8>) -> Self {
+        self.quote = quote;
+        self
+    }
+}
+
+/// Platform that exported a CSV, from its header line
+///
+/// Each platform's export has a column the others lack: `Rating Code` for Mountain Project,
+/// `Ascent Label` for theCrag and `Tries` for Vertical-Life.  Column names are matched ignoring
+/// case, quotes, and spaces versus underscores.  `None` if the header matches no platform.
+///
+/// # Examples
+/// ```
+/// use open_tick::import::detect_source;
+/// use open_tick::Source;
+///
+/// let header = "Date,Name,Grade,Type,Style,Tries,Location,Indoor,Board,Board Angle,Notes";
+/// assert_eq!(detect_source(header), Some(Source::VerticalLife));
+/// assert_eq!(detect_source("Date,Route"), None);
+/// ```
+pub fn detect_source(header: &str) -> Option<Source> {
+    let columns: Vec<String> = header
+        .trim_start_matches('\u{feff}')
+        .split([',', ';'])
+        .map(|c| {
+            c.trim()
+                .trim_matches('"')
+                .trim()
+                .replace('_', " ")
+                .to_lowercase()
+        })
+        .collect();
+    let has = |column: &str| columns.iter().any(|c| c == column);
+
+    if has("rating code") {
+        Some(Source::MountainProject)
+    } else if has("ascent label") {
+        Some(Source::TheCrag)
+    } else if has("tries") {
+        Some(Source::VerticalLife)
+    } else {
+        None
+    }
+}
+
+/// Read and convert ticks from a CSV export, stopping at the first error
+///
+/// # Examples
+/// ```
+/// use open_tick::import::collect_ticks;
+/// use open_tick::Source;
+///
+/// let csv = r#"Date,Route,Rating,Notes,URL,Pitches,Location,"Avg Stars","Your Stars",Style,"Lead Style","Route Type","Your Rating",Length,"Rating Code"
+/// 2023-06-01,"Route Name",V1,,https://www.mountainproject.com/route/271828/route-name,1,"Area > Crag",2.5,-1,Send,,Boulder,,10,20300
+/// "#;
+///
+/// let ticks = collect_ticks(csv.as_bytes(), Source::MountainProject).expect("valid export");
+/// assert_eq!(ticks.len(), 1);
+/// ```
+pub fn collect_ticks<R: Read>(r: R, source: Source) -> Result<Vec<OpenTick>, ImportError> {
+    collect_ticks_with(r, source, &ImportOptions::default())
+}
+
+/// As [`collect_ticks`], for an export already read into memory, such as an upload
+pub fn parse_str(data: &str, source: Source) -> Result<Vec<OpenTick>, ImportError> {
+    collect_ticks(data.as_bytes(), source)
+}
+
+/// As [`collect_ticks`], with the given options
+pub fn collect_ticks_with<R: Read>(
+    r: R,
+    source: Source,
+    options: &ImportOptions,
+) -> Result<Vec<OpenTick>, ImportError> {
+    ticks(r, source, options).collect()
+}
+
+/// Read and convert ticks from a CSV export, skipping rows that can't be imported
+///
+/// Returns the ticks that were imported, and the errors for the rows that were skipped.
+pub fn collect_ticks_lossy<R: Read>(r: R, source: Source) -> (Vec<OpenTick>, Vec<ImportError>) {
+    collect_ticks_lossy_with(r, source, &ImportOptions::default())
+}
+
+/// As [`collect_ticks_lossy`], with the given options
+pub fn collect_ticks_lossy_with<R: Read>(
+    r: R,
+    source: Source,
+    options: &ImportOptions,
+) -> (Vec<OpenTick>, Vec<ImportError>) {
+    let mut ticks_ok = Vec::new();
+    let mut errors = Vec::new();
+
+    for result in ticks(r, source, options) {
+        match result {
+            Ok(tick) => ticks_ok.push(tick),
+            // The underlying reader failed, so there are no more rows to be had
+            Err(ImportError::Csv(e)) if e.is_io_error() => {
+                errors.push(ImportError::Csv(e));
+                break;
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+
+    (ticks_ok, errors)
+}
+
+/// Converting an iterator of deserialized records to ticks
+///
+/// Implemented for every iterator of results whose records implement [`IntoOpenTick`], such as
+/// that of [`csv::Reader::deserialize`].  Records are converted lazily, as they are read.  Unlike
+/// [`collect_ticks`], the reader is used as configured, with no delimiter detection or trimming.
+///
+/// # Examples
+/// ```
+/// use open_tick::import::IterExt;
+/// use open_tick::MountainProjectTick;
+///
+/// let csv = r#"Date,Route,Rating,Notes,URL,Pitches,Location,"Avg Stars","Your Stars",Style,"Lead Style","Route Type","Your Rating",Length,"Rating Code"
+/// 2023-06-01,"Route Name",V1,,https://www.mountainproject.com/route/271828/route-name,1,"Area > Crag",2.5,-1,Send,,Boulder,,10,20300
+/// "#;
+///
+/// let mut reader = csv::Reader::from_reader(csv.as_bytes());
+/// let ticks: Vec<_> = reader
+///     .deserialize::<MountainProjectTick>()
+///     .open_ticks()
+///     .collect::<Result<_, _>>()
+///     .expect("valid export");
+/// assert_eq!(ticks[0].route_name.as_deref(), Some("Route Name"));
+/// ```
+pub trait IterExt<T, E>: Iterator<Item = Result<T, E>> + Sized
+where
+    T: IntoOpenTick,
+    E: Into<ImportError>,
+{
+    /// Convert each record to an [`OpenTick`], passing on errors
+    fn open_ticks(self) -> OpenTicks<Self, T, E> {
+        self.map(|record| Ok(record.map_err(Into::into)?.into_open_tick()?))
+    }
+}
+
+/// Iterator returned by [`IterExt::open_ticks`]
+pub type OpenTicks<I, T, E> = std::iter::Map<I, fn(Result<T, E>) -> Result<OpenTick, ImportError>>;
+
+impl<I, T, E> IterExt<T, E> for I
+where
+    I: Iterator<Item = Result<T, E>>,
+    T: IntoOpenTick,
+    E: Into<ImportError>,
+{
+}
+
+/// Read and convert ticks from a response of Mountain Project's JSON API
+///
+/// The response is an object with the ticks in its `ticks` array; its other fields, such as the
+/// hardest grade, are ignored.
+pub fn read_mountain_project_api<R: Read>(r: R) -> Result<Vec<OpenTick>, ImportError> {
+    #[derive(serde::Deserialize)]
+    struct Response {
+        ticks: Vec<MountainProjectApiTick>,
+    }
+
+    let response: Response = serde_json::from_reader(r)?;
+    response
+        .ticks
+        .into_iter()
+        .map(|tick| Ok(tick.into_open_tick()?))
+        .collect()
+}
+
+/// Read and convert ticks from a gzip-compressed Mountain Project export
+///
+/// As [`collect_ticks`], decompressing the export as it is read.
+#[cfg(feature = "gzip")]
+pub fn read_mountain_project_gz<R: Read>(r: R) -> Result<Vec<OpenTick>, ImportError> {
+    collect_ticks(flate2::read::GzDecoder::new(r), Source::MountainProject)
+}
+
+/// Read and convert ticks from a CSV export, converting rows in parallel
+///
+/// As [`collect_ticks`], and the ticks are in the same order.  Rows are read one after another,
+/// then converted on rayon's thread pool.  If several rows can't be imported, which of their
+/// errors is returned is unspecified.
+#[cfg(feature = "rayon")]
+pub fn par_collect_ticks<R: Read>(r: R, source: Source) -> Result<Vec<OpenTick>, ImportError> {
+    let options = ImportOptions::default();
+    let reader = csv_reader(r, &options)?;
+    let strict = options.strict;
+
+    match source {
+        Source::MountainProject => par_convert::<MountainProjectTick, _>(reader, strict),
+        Source::TheCrag => par_convert::<TheCragTick, _>(reader, strict),
+        Source::VerticalLife => par_convert::<VerticalLifeTick, _>(reader, strict),
+    }
+}
+
+fn ticks<'r, R: Read + 'r>(
+    r: R,
+    source: Source,
+    options: &ImportOptions,
+) -> Box<dyn Iterator<Item = Result<OpenTick, ImportError>> + 'r> {
+    let reader = match csv_reader(r, options) {
+        Ok(reader) => reader,
+        Err(e) => return Box::new(std::iter::once(Err(ImportError::Io(e)))),
+    };
+    let strict = options.strict;
+
+    match source {
+        Source::MountainProject => Box::new(convert::<MountainProjectTick, _>(reader, strict)),
+        Source::TheCrag => Box::new(convert::<TheCragTick, _>(reader, strict)),
+        Source::VerticalLife => Box::new(convert::<VerticalLifeTick, _>(reader, strict)),
+    }
+}
+
+/// The header line read while detecting the delimiter, followed by the rest of the export
+type Input<R> = io::Chain<io::Cursor<Vec<u8>>, BufReader<SkipBom<R>>>;
+
+/// CSV reader configured by `options`
+///
+/// Fails if the header line can't be read while detecting the delimiter.
+fn csv_reader<R: Read>(r: R, options: &ImportOptions) -> io::Result<csv::Reader<Input<R>>> {
+    let mut r = BufReader::new(SkipBom::new(r));
+    let mut header = Vec::new();
+
+    let delimiter = match options.delimiter {
+        Some(delimiter) => delimiter,
+        None => {
+            r.read_until(b'\n', &mut header)?;
+            detect_delimiter(&header)
+        }
+    };
+
+    let mut builder = csv::ReaderBuilder::new();
+    // Hand-edited files often pad cells with spaces.  Fields are trimmed by `records`, which
+    // does so without allocating a new record for every row.
+    builder
+        .flexible(true)
+        .delimiter(delimiter)
+        .trim(csv::Trim::Headers);
+    match options.quote {
+        Some(quote) => builder.quote(quote),
+        None => builder.quoting(false),
+    };
+    // The header line was consumed while detecting the delimiter, so put it back in front
+    Ok(builder.from_reader(io::Cursor::new(header).chain(r)))
+}
+
+/// `;` if it separates more fields than `,` in `header`, ignoring quoted text
+fn detect_delimiter(header: &[u8]) -> u8 {
+    let mut quoted = false;
+    let (mut commas, mut semicolons) = (0, 0);
+
+    for &byte in header {
+        match byte {
+            b'"' => quoted = !quoted,
+            b',' if !quoted => commas += 1,
+            b';' if !quoted => semicolons += 1,
+            _ => {}
+        }
+    }
+
+    if semicolons > commas {
+        b';'
+    } else {
+        b','
+    }
+}
+
+const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// Reader that skips a UTF-8 byte order mark at the start of the input
+///
+/// Spreadsheet programs often add one when saving a CSV.  The csv reader only strips it if the
+/// whole mark arrives in its first read, which isn't guaranteed for streamed input.
+struct SkipBom<R> {
+    inner: R,
+    /// Bytes read while checking for the mark, still to be passed on
+    start: Vec<u8>,
+    checked: bool,
+}
+
+impl<R> SkipBom<R> {
+    fn new(inner: R) -> Self {
+        SkipBom {
+            inner,
+            start: Vec::new(),
+            checked: false,
+        }
+    }
+}
+
+impl<R: Read> Read for SkipBom<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if !self.checked {
+            let mut start = Vec::with_capacity(UTF8_BOM.len());
+            self.inner
+                .by_ref()
+                .take(UTF8_BOM.len() as u64)
+                .read_to_end(&mut start)?;
+            if start != UTF8_BOM {
+                self.start = start;
+            }
+            self.checked = true;
+        }
+
+        if self.start.is_empty() {
+            return self.inner.read(buf);
+        }
+
+        let n = buf.len().min(self.start.len());
+        buf[..n].copy_from_slice(&self.start[..n]);
+        self.start.drain(..n);
+
+        Ok(n)
+    }
+}
+
+/// Deserialized rows of a CSV export, with padding trimmed from each field
+///
+/// Every row is read into the same pair of buffers, rather than the row's own record.
+fn records<T, R>(mut reader: csv::Reader<R>) -> impl Iterator<Item = Result<T, csv::Error>>
+where
+    T: DeserializeOwned,
+    R: Read,
+{
+    let mut error = None;
+    let headers = match reader.headers() {
+        Ok(headers) => Some(headers.clone()),
+        Err(e) => {
+            error = Some(e);
+            None
+        }
+    };
+    let (mut row, mut trimmed) = (csv::StringRecord::new(), csv::StringRecord::new());
+
+    std::iter::from_fn(move || {
+        if let Some(e) = error.take() {
+            return Some(Err(e));
+        }
+        // Without a header line there are no rows to be had
+        let headers = headers.as_ref()?;
+
+        match reader.read_record(&mut row) {
+            Ok(true) => {
+                trimmed.clear();
+                trimmed.set_position(row.position().cloned());
+                for field in row.iter() {
+                    trimmed.push_field(field.trim());
+                }
+                Some(trimmed.deserialize(Some(headers)))
+            }
+            Ok(false) => None,
+            Err(e) => Some(Err(e)),
+        }
+    })
+}
+
+fn convert<T, R>(
+    reader: csv::Reader<R>,
+    strict: bool,
+) -> impl Iterator<Item = Result<OpenTick, ImportError>>
+where
+    T: DeserializeOwned + IntoOpenTick + Unrecognized,
+    R: Read,
+{
+    records::<T, R>(reader).map(move |record| convert_record(record?, strict))
+}
+
+#[cfg(feature = "rayon")]
+fn par_convert<T, R>(reader: csv::Reader<R>, strict: bool) -> Result<Vec<OpenTick>, ImportError>
+where
+    T: DeserializeOwned + IntoOpenTick + Unrecognized + Send,
+    R: Read,
+{
+    use rayon::prelude::*;
+
+    let records = records::<T, R>(reader).collect::<Result<Vec<T>, _>>()?;
+
+    records
+        .into_par_iter()
+        .map(|record| convert_record(record, strict))
+        .collect()
+}
+
+fn convert_record<T: IntoOpenTick + Unrecognized>(
+    record: T,
+    strict: bool,
+) -> Result<OpenTick, ImportError> {
+    if strict {
+        if let Some(value) = record.unrecognized_value() {
+            return Err(ImportError::Unrecognized(value.to_string()));
+        }
+    }
+
+    Ok(record.into_open_tick()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    // The second row has a non-numeric pitch count
+    const MP_CSV_ONE_BAD_ROW: &str = r#"Date,Route,Rating,Notes,URL,Pitches,Location,"Avg Stars","Your Stars",Style,"Lead Style","Route Type","Your Rating",Length,"Rating Code"
+2023-06-01,"Route Name",V1,,https://www.mountainproject.com/route/271828/route-name,1,"Area > Crag",2.5,-1,Send,,Boulder,,10,20300
+2023-06-02,"Bad Route",V2,,https://www.mountainproject.com/route/314159/bad-route,many,"Area > Crag",2.5,-1,Send,,Boulder,,10,20300
+2023-06-03,"Other Route",V3,,https://www.mountainproject.com/route/161803/other-route,1,"Area > Crag",2.5,-1,Send,,Boulder,,10,20300
+"#;
+
+    #[test]
+    fn collect_stops_at_bad_row() {
+        let result = collect_ticks(MP_CSV_ONE_BAD_ROW.as_bytes(), Source::MountainProject);
+
+        assert!(matches!(result, Err(ImportError::Csv(_))));
+    }
+
+    #[test]
+    fn bad_row_position() {
+        let result = collect_ticks(MP_CSV_ONE_BAD_ROW.as_bytes(), Source::MountainProject);
+
+        let Err(ImportError::Csv(e)) = result else {
+            panic!("expected a CSV error, got {result:?}");
+        };
+        assert_eq!(e.position().map(|p| p.line()), Some(3));
+    }
+
+    #[test]
+    fn collect_lossy_skips_bad_row() {
+        let (ticks, errors) =
+            collect_ticks_lossy(MP_CSV_ONE_BAD_ROW.as_bytes(), Source::MountainProject);
+
+        assert_eq!(ticks.len(), 2);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(ticks[0].route_name.as_deref(), Some("Route Name"));
+        assert_eq!(ticks[1].route_name.as_deref(), Some("Other Route"));
+    }
+
+    #[test]
+    fn strict_rejects_unknown_style() {
+        let mp_csv = r#"Date,Route,Rating,Notes,URL,Pitches,Location,"Avg Stars","Your Stars",Style,"Lead Style","Route Type","Your Rating",Length,"Rating Code"
+2023-06-01,"Route Name",V1,,https://www.mountainproject.com/route/271828/route-name,1,"Area > Crag",2.5,-1,Crawl,,Boulder,,10,20300
+"#;
+
+        let lenient = collect_ticks(mp_csv.as_bytes(), Source::MountainProject);
+        let strict = collect_ticks_with(
+            mp_csv.as_bytes(),
+            Source::MountainProject,
+            &ImportOptions::default().strict(true),
+        );
+
+        assert!(lenient.is_ok());
+        assert!(matches!(strict, Err(ImportError::Unrecognized(v)) if v == "Crawl"));
+    }
+
+    /// Reader returning a single byte at a time, as a slow stream might
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match (self.0.split_first(), buf.first_mut()) {
+                (Some((&byte, rest)), Some(first)) => {
+                    *first = byte;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    #[test]
+    fn byte_order_mark() -> Result<(), ImportError> {
+        let mp_csv = format!(
+            "\u{feff}{}",
+            MP_CSV_ONE_BAD_ROW
+                .lines()
+                .take(2)
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+
+        let ticks = collect_ticks(mp_csv.as_bytes(), Source::MountainProject)?;
+        let trickled = collect_ticks(Trickle(mp_csv.as_bytes()), Source::MountainProject)?;
+
+        let date = chrono::NaiveDate::from_ymd_opt(2023, 6, 1);
+        assert_eq!(ticks[0].date, date);
+        assert_eq!(trickled[0].date, date);
+
+        Ok(())
+    }
+
+    #[test]
+    fn short_input_without_mark() -> io::Result<()> {
+        let mut out = String::new();
+        SkipBom::new(Trickle(b"ab")).read_to_string(&mut out)?;
+
+        assert_eq!(out, "ab");
+
+        Ok(())
+    }
+
+    #[test]
+    fn notes_with_commas() -> Result<(), ImportError> {
+        let mp_csv = r#"Date,Route,Rating,Notes,URL,Pitches,Location,"Avg Stars","Your Stars",Style,"Lead Style","Route Type","Your Rating",Length,"Rating Code"
+2023-06-01,"Route Name",5.11a,"Steep, pumpy, amazing",https://www.mountainproject.com/route/271828/route-name,2,"Area > Crag",2.5,-1,Lead,Redpoint,Sport,5.11b,80,20300
+"#;
+
+        let ticks = collect_ticks(mp_csv.as_bytes(), Source::MountainProject)?;
+
+        assert_eq!(ticks[0].comment.as_deref(), Some("Steep, pumpy, amazing"));
+        assert_eq!(ticks[0].route_location.as_deref(), Some("Area > Crag"));
+        assert_eq!(ticks[0].ascent_grade.as_deref(), Some("5.11b"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn semicolon_delimiter() -> Result<(), ImportError> {
+        let mp_csv = r#"Date;Route;Rating;Notes;URL;Pitches;Location;"Avg Stars";"Your Stars";Style;"Lead Style";"Route Type";"Your Rating";Length;"Rating Code"
+2023-06-01;"Route Name";V1;"Short, sharp";https://www.mountainproject.com/route/271828/route-name;1;"Area > Crag";2,5;-1;Send;;Boulder;;10;20300
+"#;
+
+        let detected = collect_ticks(mp_csv.as_bytes(), Source::MountainProject)?;
+        let explicit = collect_ticks_with(
+            mp_csv.as_bytes(),
+            Source::MountainProject,
+            &ImportOptions::default().delimiter(b';'),
+        )?;
+
+        assert_eq!(detected[0].route_name.as_deref(), Some("Route Name"));
+        assert_eq!(detected[0].comment.as_deref(), Some("Short, sharp"));
+        assert_eq!(explicit[0].comment, detected[0].comment);
+
+        Ok(())
+    }
+
+    #[test]
+    fn mountain_project_api_response() -> Result<(), ImportError> {
+        let json = r#"{
+            "hardest": "5.9",
+            "ticks": [{
+                "date": "2023-06-01",
+                "style": "Lead",
+                "leadStyle": "Onsight",
+                "route": {"id": 105748391, "name": "The Nose", "type": "Trad", "rating": "5.9"}
+            }],
+            "success": 1
+        }"#;
+
+        let ticks = read_mountain_project_api(json.as_bytes())?;
+
+        assert_eq!(ticks.len(), 1);
+        assert_eq!(ticks[0].route_name.as_deref(), Some("The Nose"));
+        assert!(matches!(
+            read_mountain_project_api(r#"{"hardest": "5.9"}"#.as_bytes()),
+            Err(ImportError::Json(_))
+        ));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn gzipped_export() -> Result<(), Box<dyn std::error::Error>> {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(
+            MP_CSV_ONE_BAD_ROW
+                .lines()
+                .take(2)
+                .collect::<Vec<_>>()
+                .join("\n")
+                .as_bytes(),
+        )?;
+        let gz = encoder.finish()?;
+
+        let ticks = read_mountain_project_gz(gz.as_slice())?;
+
+        assert_eq!(ticks.len(), 1);
+        assert_eq!(ticks[0].route_name.as_deref(), Some("Route Name"));
+
+        Ok(())
+    }
+}
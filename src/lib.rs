@@ -1,28 +1,82 @@
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::NaiveDate;
+#[cfg(feature = "serde")]
+use chrono::{NaiveTime, Utc};
+use grade::{Grade, GradeParseStatus, GradePreferences};
+use std::cmp::Ordering;
+#[cfg(feature = "provenance")]
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
 
+#[cfg(feature = "serde")]
+mod de;
+pub mod dedup;
+#[cfg(feature = "serde")]
+pub mod export;
+pub mod grade;
+#[cfg(feature = "serde")]
+pub mod import;
+pub mod logbook;
+#[cfg(feature = "serde")]
 pub mod mountain_project;
+pub mod prelude;
+pub mod stats;
+#[cfg(feature = "serde")]
 pub mod thecrag;
+pub mod util;
+#[cfg(feature = "serde")]
+pub mod vertical_life;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
-use mountain_project::MountainProjectRouteType;
+#[cfg(feature = "serde")]
+use de::OrOther;
+#[cfg(feature = "serde")]
 pub use mountain_project::MountainProjectTick;
-use thecrag::TheCragGearStyle;
+#[cfg(feature = "serde")]
+use mountain_project::{
+    MountainProjectLeadStyle, MountainProjectRouteId, MountainProjectRouteType,
+    MountainProjectStyle,
+};
+#[cfg(feature = "serde")]
 pub use thecrag::TheCragTick;
+#[cfg(feature = "serde")]
+use thecrag::{TheCragAscentType, TheCragGearStyle, TheCragRouteId};
+#[cfg(feature = "serde")]
+pub use vertical_life::VerticalLifeTick;
+#[cfg(feature = "serde")]
+use vertical_life::{VerticalLifeStyle, VerticalLifeType};
 
 /// A tick
 ///
 /// This struct is non-exhaustive; it will likely gain more fields in future.
 /// # Examples
 #[non_exhaustive]
-#[derive(Debug)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct OpenTick {
     /// Date the climbing happened
     ///
     /// May be extended in future to account for multi-day ascents and more precise times.
     pub date: Option<NaiveDate>,
+    /// Year or month of the climbing, when the source doesn't record the day
+    ///
+    /// [`OpenTick::date`] is `None` for such ticks.
+    pub partial_date: Option<PartialDate>,
     /// Name of the route
     pub route_name: Option<String>,
     /// Location of the route
     pub route_location: Option<String>,
+    /// Structured location of the route
+    pub location: Option<Location>,
+    /// Link to the route on the source platform
+    pub route_url: Option<String>,
+    /// Latitude and longitude of the route, in degrees
+    ///
+    /// No supported platform's export records these, but they may be filled in from a lookup of
+    /// the route's location.
+    pub coordinates: Option<(f64, f64)>,
     /// Type of route as most often climbed
     pub route_discipline: Option<Discipline>,
     /// Type of route as climbed in this ascent
@@ -31,137 +85,2053 @@ pub struct OpenTick {
     pub route_grade: Option<String>,
     /// Personal grade, for this ascent
     pub ascent_grade: Option<String>,
+    /// Average quality rating of the route, in stars on the source platform's scale
+    ///
+    /// Mountain Project rates routes from 0 to 4 stars and theCrag from 0 to 5.
+    pub route_stars: Option<f32>,
+    /// Quality of the route as a percentage, for platforms that rate it apart from stars
+    ///
+    /// theCrag rates quality as a percentage, such as 83%, alongside its star count.
+    pub route_quality: Option<f32>,
+    /// Style of this ascent
+    pub ascent_style: Option<AscentStyle>,
     /// Free-form comments
     pub comment: Option<String>,
+    /// People climbed with
+    pub partners: Option<Vec<String>>,
+    /// Number of attempts, including the one ticked, if the source or a `#attempts:N` tag in the
+    /// comment records it
+    pub attempts: Option<u32>,
+    /// Number of pitches climbed
+    pub pitches: Option<u16>,
+    /// Whether the climb was in a gym
+    pub indoor: Option<bool>,
+    /// Training board the climb was set on
+    pub board: Option<TrainingBoard>,
+    /// Platform the tick was imported from
+    pub source: Option<Source>,
+    /// The record this tick was converted from
+    #[cfg(feature = "raw")]
+    pub raw: Option<RawTick>,
+    /// Platform that supplied each field, by field name, as recorded by [`OpenTick::merge`]
+    #[cfg(feature = "provenance")]
+    pub provenance: HashMap<&'static str, Source>,
 }
 
-/// Disciplines
+impl OpenTick {
+    /// Whether two ticks record the same ascent
+    ///
+    /// Compares only the fields that identify an ascent: the date, or the partial date if only
+    /// that is known, the normalized route name, and the normalized crag, i.e. the most specific
+    /// part of the location.  Locations are only compared when both ticks have one, since
+    /// platforms don't share an area hierarchy.  Other fields, such as comments and the source,
+    /// are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::OpenTick;
+    ///
+    /// let mut a = OpenTick::default();
+    /// a.route_name = Some("The Nose".to_string());
+    /// a.comment = Some("Long day".to_string());
+    ///
+    /// let mut b = a.clone();
+    /// b.route_name = Some("Nose, The".to_string());
+    /// b.comment = None;
+    ///
+    /// assert!(a.same_ascent(&b));
+    /// ```
+    pub fn same_ascent(&self, other: &OpenTick) -> bool {
+        let locations_match = match (self.normalized_crag(), other.normalized_crag()) {
+            (Some(a), Some(b)) => a == b,
+            _ => true,
+        };
+
+        let date = |tick: &OpenTick| tick.date.map(PartialDate::Full).or(tick.partial_date);
+
+        date(self) == date(other)
+            && self.route_name.as_deref().map(util::normalize_name)
+                == other.route_name.as_deref().map(util::normalize_name)
+            && locations_match
+    }
+
+    /// A hash of the fields that identify the ascent, for telling whether a tick was seen before
+    ///
+    /// Hashes the date, the normalized route name and crag, as compared by
+    /// [`OpenTick::same_ascent`], and the source.  Other fields, such as comments, don't affect
+    /// it, so an edited tick keeps its hash.  The hash is computed with a fixed algorithm, so it
+    /// is the same across runs, platforms and versions of Rust, and can be stored.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::OpenTick;
+    ///
+    /// let mut tick = OpenTick::default();
+    /// tick.route_name = Some("The Nose".to_string());
+    ///
+    /// let mut edited = tick.clone();
+    /// edited.comment = Some("Long day".to_string());
+    ///
+    /// assert_eq!(tick.identity_hash(), edited.identity_hash());
+    /// ```
+    pub fn identity_hash(&self) -> u64 {
+        let date = self
+            .date
+            .map(PartialDate::Full)
+            .or(self.partial_date)
+            .map(|d| d.to_string());
+        let source = self.source.map(|s| match s {
+            Source::MountainProject => "mountain project",
+            Source::TheCrag => "thecrag",
+            Source::VerticalLife => "vertical-life",
+        });
+        let fields = [
+            date,
+            self.route_name.as_deref().map(util::normalize_name),
+            self.normalized_crag(),
+            source.map(String::from),
+        ];
+
+        // 64-bit FNV-1a, with a marker byte before each field so that fields can't run together
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for field in &fields {
+            let bytes = match field {
+                Some(field) => [&[1][..], field.as_bytes()].concat(),
+                None => vec![0],
+            };
+            for byte in bytes {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+
+        hash
+    }
+
+    /// The normalized crag, i.e. the most specific part of the location
+    pub(crate) fn normalized_crag(&self) -> Option<String> {
+        self.location
+            .as_ref()
+            .and_then(|l| l.crag.clone())
+            .or_else(|| util::parse_area_path(self.route_location.as_deref()?).pop())
+            .as_deref()
+            .map(util::normalize_name)
+    }
+
+    /// Merge two records of the same ascent
+    ///
+    /// Fields populated in `self` are kept, and the rest are filled from `other`.  Empty strings
+    /// count as unpopulated, since some platforms export blank fields rather than omitting them.
+    /// When both ticks have a comment or grade, `policy` decides which to keep.
+    ///
+    /// With the `provenance` feature, the platform that supplied each populated field is recorded
+    /// in [`OpenTick::provenance`].
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::{MergePolicy, OpenTick};
+    ///
+    /// let mut a = OpenTick::default();
+    /// a.route_name = Some("The Nose".to_string());
+    ///
+    /// let mut b = OpenTick::default();
+    /// b.route_name = Some("Nose, The".to_string());
+    /// b.route_grade = Some("5.9 C2".to_string());
+    ///
+    /// let merged = a.merge(b, MergePolicy::PreferFirst);
+    /// assert_eq!(merged.route_name.as_deref(), Some("The Nose"));
+    /// assert_eq!(merged.route_grade.as_deref(), Some("5.9 C2"));
+    /// ```
+    pub fn merge(mut self, other: OpenTick, policy: MergePolicy) -> OpenTick {
+        #[cfg_attr(not(feature = "provenance"), allow(unused_variables))]
+        let suppliers = [
+            ("date", fill(&mut self.date, other.date)),
+            (
+                "partial_date",
+                fill(&mut self.partial_date, other.partial_date),
+            ),
+            ("route_name", fill(&mut self.route_name, other.route_name)),
+            (
+                "route_location",
+                fill(&mut self.route_location, other.route_location),
+            ),
+            ("location", fill(&mut self.location, other.location)),
+            ("route_url", fill(&mut self.route_url, other.route_url)),
+            (
+                "coordinates",
+                fill(&mut self.coordinates, other.coordinates),
+            ),
+            (
+                "route_discipline",
+                fill(&mut self.route_discipline, other.route_discipline),
+            ),
+            (
+                "ascent_discipline",
+                fill(&mut self.ascent_discipline, other.ascent_discipline),
+            ),
+            (
+                "route_grade",
+                fill_text(&mut self.route_grade, other.route_grade, policy, false),
+            ),
+            (
+                "ascent_grade",
+                fill_text(&mut self.ascent_grade, other.ascent_grade, policy, false),
+            ),
+            (
+                "route_stars",
+                fill(&mut self.route_stars, other.route_stars),
+            ),
+            (
+                "route_quality",
+                fill(&mut self.route_quality, other.route_quality),
+            ),
+            (
+                "ascent_style",
+                fill(&mut self.ascent_style, other.ascent_style),
+            ),
+            (
+                "comment",
+                fill_text(&mut self.comment, other.comment, policy, true),
+            ),
+            ("partners", fill(&mut self.partners, other.partners)),
+            ("attempts", fill(&mut self.attempts, other.attempts)),
+            ("pitches", fill(&mut self.pitches, other.pitches)),
+            ("indoor", fill(&mut self.indoor, other.indoor)),
+            ("board", fill(&mut self.board, other.board)),
+        ];
+
+        #[cfg(feature = "provenance")]
+        {
+            let mut provenance = HashMap::new();
+            for (field, supplier) in suppliers {
+                let source = match supplier {
+                    Some(Supplier::Ours) => self.provenance.get(field).copied().or(self.source),
+                    Some(Supplier::Theirs) => other.provenance.get(field).copied().or(other.source),
+                    None => None,
+                };
+                if let Some(source) = source {
+                    provenance.insert(field, source);
+                }
+            }
+            self.provenance = provenance;
+        }
+
+        #[cfg(feature = "raw")]
+        fill(&mut self.raw, other.raw);
+        self.source = self.source.or(other.source);
+
+        self
+    }
+
+    /// The climber's own grade for this ascent if they gave one, otherwise the route's
+    ///
+    /// Analyses of a climber's ascents should usually prefer their own assessment.  Blank grades
+    /// count as missing, since some platforms export an empty personal grade.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::OpenTick;
+    ///
+    /// let mut tick = OpenTick::default();
+    /// tick.route_grade = Some("5.10a".to_string());
+    /// tick.ascent_grade = Some(String::new());
+    /// assert_eq!(tick.effective_grade(), Some("5.10a"));
+    ///
+    /// tick.ascent_grade = Some("5.10b".to_string());
+    /// assert_eq!(tick.effective_grade(), Some("5.10b"));
+    /// ```
+    pub fn effective_grade(&self) -> Option<&str> {
+        [&self.ascent_grade, &self.route_grade]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .find(|g| !g.trim().is_empty())
+    }
+
+    /// The [effective grade](OpenTick::effective_grade), parsed
+    ///
+    /// As for [`OpenTick::normalized_grade`], the first recognized word counts, so danger ratings
+    /// like `PG13` are ignored.  `None` if there is no grade or it isn't recognized.
+    pub fn effective_grade_parsed(&self) -> Option<Grade> {
+        recognized_grade(self.effective_grade()?)
+    }
+
+    /// This tick with its route's grade replaced, such as to correct a misparsed grade
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::OpenTick;
+    ///
+    /// let mut tick = OpenTick::default();
+    /// tick.route_grade = Some("5.12".to_string());
+    ///
+    /// let tick = tick.with_route_grade("5.12a");
+    /// assert_eq!(tick.route_grade.as_deref(), Some("5.12a"));
+    /// ```
+    pub fn with_route_grade(mut self, grade: impl Into<String>) -> OpenTick {
+        self.route_grade = Some(grade.into());
+        self
+    }
+
+    /// This tick with the climber's own grade for the ascent replaced
+    ///
+    /// See [`OpenTick::with_route_grade`].
+    pub fn with_ascent_grade(mut self, grade: impl Into<String>) -> OpenTick {
+        self.ascent_grade = Some(grade.into());
+        self
+    }
+
+    /// How the climber's grade for this ascent compares to the route's consensus grade
+    ///
+    /// `Greater` if the climber found it harder than graded, i.e. sandbagged, and `Less` if they
+    /// found it soft.  As for [`OpenTick::normalized_grade`], the first recognized word of each
+    /// grade counts.  `None` if either grade is missing, isn't recognized, or the two are in
+    /// different systems.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::OpenTick;
+    /// use std::cmp::Ordering;
+    ///
+    /// let mut tick = OpenTick::default();
+    /// tick.route_grade = Some("6a".to_string());
+    /// tick.ascent_grade = Some("6a+".to_string());
+    ///
+    /// assert_eq!(tick.sandbagged(), Some(Ordering::Greater));
+    /// ```
+    pub fn sandbagged(&self) -> Option<Ordering> {
+        let route = recognized_grade(self.route_grade.as_deref()?)?;
+        let ascent = recognized_grade(self.ascent_grade.as_deref()?)?;
+
+        ascent.cmp_same_system(&route)
+    }
+
+    /// How many grades harder than the route's grade the climber found it
+    ///
+    /// The difference between the [ordinals](Grade::ordinal) of the climber's grade and the
+    /// route's, so positive if [sandbagged](OpenTick::sandbagged) and negative if soft.  A range
+    /// counts as the middle of its bounds.  `None` in the same cases as for
+    /// [`OpenTick::sandbagged`].
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::OpenTick;
+    ///
+    /// let mut tick = OpenTick::default();
+    /// tick.route_grade = Some("5.10a".to_string());
+    /// tick.ascent_grade = Some("5.10b/c".to_string());
+    ///
+    /// assert_eq!(tick.grade_offset(), Some(1.5));
+    /// ```
+    pub fn grade_offset(&self) -> Option<f32> {
+        let (route_system, route_low, route_high) =
+            recognized_grade(self.route_grade.as_deref()?)?.bounds()?;
+        let (ascent_system, ascent_low, ascent_high) =
+            recognized_grade(self.ascent_grade.as_deref()?)?.bounds()?;
+
+        (route_system == ascent_system).then(|| {
+            (ascent_low + ascent_high) as f32 / 2.0 - (route_low + route_high) as f32 / 2.0
+        })
+    }
+
+    /// The route's grade in the system preferred for its discipline
+    ///
+    /// For a route in several disciplines, the first preferred system the grade converts to is
+    /// used.  Without a known discipline, the discipline is guessed from the grade, so a route
+    /// graded on the V-scale is taken to be a boulder.  The first recognized word of the grade is
+    /// used, so danger ratings like `PG13` are ignored.  `None` if the grade isn't recognized or
+    /// can't be converted.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::grade::{Grade, GradePreferences, GradeSystem};
+    /// use open_tick::{DisciplineKind, OpenTick};
+    ///
+    /// let mut tick = OpenTick::default();
+    /// tick.route_grade = Some("6a".to_string());
+    /// tick.route_discipline = Some("sport".parse().unwrap());
+    ///
+    /// assert_eq!(
+    ///     tick.normalized_grade(&GradePreferences::default()),
+    ///     Some(Grade::parse("5.10a"))
+    /// );
+    ///
+    /// let french = GradePreferences::default().with(DisciplineKind::Sport, GradeSystem::French);
+    /// assert_eq!(tick.normalized_grade(&french), Some(Grade::parse("6a")));
+    /// ```
+    pub fn normalized_grade(&self, preferences: &GradePreferences) -> Option<Grade> {
+        let grade = self.recognized_route_grade()?;
+
+        let mut kinds: Vec<DisciplineKind> = self
+            .route_discipline
+            .iter()
+            .flat_map(Discipline::iter)
+            .filter(|&k| k != DisciplineKind::Unknown)
+            .collect();
+        if kinds.is_empty() {
+            kinds = grade.system()?.applies_to().to_vec();
+        }
+
+        kinds
+            .into_iter()
+            .find_map(|kind| grade.to_system(preferences.system_for(kind)).ok())
+    }
+
+    /// Whether the route's grade is in a system this crate recognizes
+    ///
+    /// As for [`OpenTick::normalized_grade`], the first recognized word of the grade counts, so
+    /// `5.10a PG13` is recognized as YDS.  Grades in local or exotic systems come out
+    /// [`GradeParseStatus::Unrecognized`], for the user to handle by hand.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::grade::{GradeParseStatus, GradeSystem};
+    /// use open_tick::OpenTick;
+    ///
+    /// let mut tick = OpenTick::default();
+    /// assert_eq!(tick.grade_parse_status(), GradeParseStatus::Missing);
+    ///
+    /// tick.route_grade = Some("HVS".to_string());
+    /// assert_eq!(tick.grade_parse_status(), GradeParseStatus::Unrecognized);
+    ///
+    /// tick.route_grade = Some("5.10a PG13".to_string());
+    /// assert_eq!(
+    ///     tick.grade_parse_status(),
+    ///     GradeParseStatus::Parsed(GradeSystem::Yds)
+    /// );
+    /// ```
+    pub fn grade_parse_status(&self) -> GradeParseStatus {
+        match self.route_grade.as_deref() {
+            None | Some("") => GradeParseStatus::Missing,
+            Some(_) => match self.recognized_route_grade().and_then(|g| g.system()) {
+                Some(system) => GradeParseStatus::Parsed(system),
+                None => GradeParseStatus::Unrecognized,
+            },
+        }
+    }
+
+    /// The route's grade, or its first word that is a recognized grade
+    fn recognized_route_grade(&self) -> Option<Grade> {
+        recognized_grade(self.route_grade.as_deref()?)
+    }
+
+    /// The route's ID on the platform of [`OpenTick::route_url`]
+    ///
+    /// `None` if there is no URL, or it isn't a route on a supported platform.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::mountain_project::MountainProjectRouteId;
+    /// use open_tick::{OpenTick, RouteRef};
+    ///
+    /// let mut tick = OpenTick::default();
+    /// tick.route_url = Some("https://www.mountainproject.com/route/105748391/classic".to_string());
+    ///
+    /// assert_eq!(
+    ///     tick.route_id(),
+    ///     Some(RouteRef::MountainProject(MountainProjectRouteId(105748391)))
+    /// );
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn route_id(&self) -> Option<RouteRef> {
+        let url = url::Url::parse(self.route_url.as_deref()?).ok()?;
+
+        match url.domain()? {
+            "www.mountainproject.com" => MountainProjectRouteId::try_from(url)
+                .ok()
+                .map(RouteRef::MountainProject),
+            "www.thecrag.com" => TheCragRouteId::from_url(&url).map(RouteRef::TheCrag),
+            _ => None,
+        }
+    }
+
+    /// Whether this ascent was a send, an attempt, or a repeat
+    ///
+    /// Derived from [`OpenTick::ascent_style`] as by [`AscentStyle::outcome`].  Only theCrag
+    /// marks repeats in the tick itself.  Telling a repeated Mountain Project send from the first
+    /// takes the route's other ticks; see [`stats::outcomes`].
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::{AscentStyle, OpenTick, Outcome};
+    ///
+    /// let mut tick = OpenTick::default();
+    /// assert_eq!(tick.outcome(), None);
+    ///
+    /// tick.ascent_style = Some(AscentStyle::Redpoint);
+    /// assert_eq!(tick.outcome(), Some(Outcome::Send));
+    /// ```
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.ascent_style.as_ref().map(AscentStyle::outcome)
+    }
+
+    /// Number of [partners](OpenTick::partners) climbed with, 0 if none are recorded
+    pub fn partner_count(&self) -> usize {
+        self.partners.as_ref().map_or(0, Vec::len)
+    }
+}
+
+/// `text` as a grade, or its first word that is a recognized grade
+fn recognized_grade(text: &str) -> Option<Grade> {
+    std::iter::once(text)
+        .chain(text.split_whitespace())
+        .map(Grade::parse)
+        .find(|g| g.system().is_some())
+}
+
+/// One-line summary, e.g. `2020-01-01 A Route Name (5.11, sport) — onsight`
+///
+/// Shows the ascent grade and discipline if known, otherwise the route's.  Missing fields are left
+/// out.
+impl fmt::Display for OpenTick {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(date) = self.date.map(PartialDate::Full).or(self.partial_date) {
+            write!(f, "{date} ")?;
+        }
+        f.write_str(self.route_name.as_deref().unwrap_or("Unnamed route"))?;
+
+        let grade = self.effective_grade();
+        let disciplines = self
+            .ascent_discipline
+            .as_ref()
+            .or(self.route_discipline.as_ref())
+            .map(|d| {
+                d.iter()
+                    .map(|k| k.to_string())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .filter(|d| !d.is_empty());
+        let details: Vec<&str> = grade.into_iter().chain(disciplines.as_deref()).collect();
+        if !details.is_empty() {
+            write!(f, " ({})", details.join(", "))?;
+        }
+
+        if let Some(style) = self.ascent_style {
+            write!(f, " — {style}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// How [`OpenTick::merge`] resolves a comment or grade that both ticks have
 #[non_exhaustive]
-#[derive(Debug, Default, PartialEq)]
-pub struct Discipline {
-    aid: bool,
-    bouldering: bool,
-    deep_water_solo: bool,
-    ice: bool,
-    sport: bool,
-    top_rope: bool,
-    trad: bool,
-    unknown: bool,
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Keep the first tick's
+    #[default]
+    PreferFirst,
+    /// Keep the longer, e.g. the more detailed comment or the more specific grade
+    PreferLonger,
+    /// Keep both comments, one per line, unless they are identical
+    ///
+    /// Grades can't be combined, so the first tick's is kept.
+    Concatenate,
 }
 
-impl From<MountainProjectRouteType> for Discipline {
-    fn from(value: MountainProjectRouteType) -> Self {
-        Discipline {
-            aid: false, // TODO
-            bouldering: value.boulder,
-            deep_water_solo: false, // TODO
-            ice: false,             // TODO
-            sport: value.sport,
-            trad: value.trad,
-            top_rope: value.top_rope,
-            unknown: value.unknown,
+/// Separates comments joined by [`MergePolicy::Concatenate`]
+const COMMENT_SEPARATOR: &str = "\n";
+
+/// Which of two merged ticks supplied a field
+#[derive(Clone, Copy)]
+enum Supplier {
+    Ours,
+    Theirs,
+}
+
+/// Fill `ours` from `theirs` if it isn't populated, returning which supplied the result
+fn fill<T: Populated>(ours: &mut Option<T>, theirs: Option<T>) -> Option<Supplier> {
+    if ours.as_ref().is_some_and(T::is_populated) {
+        Some(Supplier::Ours)
+    } else if theirs.as_ref().is_some_and(T::is_populated) {
+        *ours = theirs;
+        Some(Supplier::Theirs)
+    } else {
+        None
+    }
+}
+
+/// As [`fill`], resolving text that both have according to `policy`
+///
+/// A concatenation counts as supplied by `ours`.
+fn fill_text(
+    ours: &mut Option<String>,
+    theirs: Option<String>,
+    policy: MergePolicy,
+    joinable: bool,
+) -> Option<Supplier> {
+    let (Some(a), Some(b)) = (
+        ours.as_deref().filter(|a| a.is_populated()),
+        theirs.as_deref().filter(|b| b.is_populated()),
+    ) else {
+        return fill(ours, theirs);
+    };
+
+    match policy {
+        MergePolicy::PreferLonger if b.chars().count() > a.chars().count() => {
+            *ours = theirs;
+            Some(Supplier::Theirs)
         }
+        MergePolicy::Concatenate if joinable && !a.split(COMMENT_SEPARATOR).any(|c| c == b) => {
+            *ours = Some(format!("{a}{COMMENT_SEPARATOR}{b}"));
+            Some(Supplier::Ours)
+        }
+        _ => Some(Supplier::Ours),
     }
 }
 
-impl From<TheCragGearStyle> for Discipline {
-    fn from(value: TheCragGearStyle) -> Self {
-        match value {
-            TheCragGearStyle::Aid => Discipline {
-                aid: true,
-                ..Default::default()
-            },
-            TheCragGearStyle::Boulder => Discipline {
-                bouldering: true,
-                ..Default::default()
-            },
-            TheCragGearStyle::Sport => Discipline {
-                sport: true,
-                ..Default::default()
-            },
-            TheCragGearStyle::TopRope => Discipline {
-                top_rope: true,
-                ..Default::default()
-            },
-            TheCragGearStyle::Trad => Discipline {
-                trad: true,
-                ..Default::default()
-            },
-            TheCragGearStyle::Unknown => Discipline {
-                unknown: true,
-                ..Default::default()
-            },
-            _ => Discipline {
-                ..Default::default()
-            },
+/// Values that may be present but carry no information
+trait Populated {
+    fn is_populated(&self) -> bool {
+        true
+    }
+}
+
+impl<T> Populated for Vec<T> {
+    fn is_populated(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+impl Populated for String {
+    fn is_populated(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+impl Populated for str {
+    fn is_populated(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+impl Populated for bool {}
+impl Populated for u16 {}
+impl Populated for u32 {}
+impl Populated for f32 {}
+impl Populated for (f64, f64) {}
+impl Populated for NaiveDate {}
+impl Populated for PartialDate {}
+impl Populated for Location {}
+impl Populated for Discipline {}
+impl Populated for AscentStyle {}
+impl Populated for TrainingBoard {}
+#[cfg(feature = "raw")]
+impl Populated for RawTick {}
+
+/// A date that may be known only to the year or month
+///
+/// Parsed from `YYYY`, `YYYY-MM` or `YYYY-MM-DD`, and displayed the same way.
+///
+/// # Examples
+/// ```
+/// use open_tick::PartialDate;
+///
+/// let date: PartialDate = "2019-06".parse().unwrap();
+/// assert_eq!(date, PartialDate::YearMonth(2019, 6));
+/// assert_eq!(date.to_string(), "2019-06");
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartialDate {
+    Year(i32),
+    /// A year, and a month from 1 to 12
+    YearMonth(i32, u32),
+    Full(NaiveDate),
+}
+
+impl PartialDate {
+    /// The full date, if the day is known
+    pub fn full(&self) -> Option<NaiveDate> {
+        match self {
+            PartialDate::Full(date) => Some(*date),
+            _ => None,
         }
     }
 }
 
-impl TryFrom<MountainProjectTick> for OpenTick {
-    type Error = ConversionError;
+impl FromStr for PartialDate {
+    type Err = PartialDateParseError;
 
-    fn try_from(value: MountainProjectTick) -> Result<Self, Self::Error> {
-        let date = value.date;
-        let route_name = Some(value.route);
-        let route_location = Some(value.location);
-        let route_discipline = Some(Discipline::from(MountainProjectRouteType::from(
-            value.route_type,
-        )));
-        let ascent_discipline = None;
-        let route_grade = Some(value.rating);
-        let ascent_grade = Some(value.your_rating);
-        let comment = Some(value.notes);
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let unrecognized = || PartialDateParseError::Unrecognized(s.to_string());
+        let year = |y: &str| {
+            (y.len() == 4 && y.chars().all(|c| c.is_ascii_digit()))
+                .then(|| y.parse().ok())
+                .flatten()
+        };
 
-        Ok(OpenTick {
-            date,
-            route_name,
-            route_location,
-            route_discipline,
-            ascent_discipline,
-            route_grade,
-            ascent_grade,
-            comment,
-        })
+        match s.split('-').collect::<Vec<_>>().as_slice() {
+            [y] => year(y).map(PartialDate::Year).ok_or_else(unrecognized),
+            [y, m] => {
+                let month = m.parse().ok().filter(|m| (1..=12).contains(m));
+                year(y)
+                    .zip(month.filter(|_| m.len() == 2))
+                    .map(|(y, m)| PartialDate::YearMonth(y, m))
+                    .ok_or_else(unrecognized)
+            }
+            _ => s.parse().map(PartialDate::Full).map_err(|_| unrecognized()),
+        }
     }
 }
 
-impl TryFrom<TheCragTick> for OpenTick {
-    type Error = ConversionError;
+impl fmt::Display for PartialDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PartialDate::Year(year) => write!(f, "{year:04}"),
+            PartialDate::YearMonth(year, month) => write!(f, "{year:04}-{month:02}"),
+            PartialDate::Full(date) => date.fmt(f),
+        }
+    }
+}
 
-    fn try_from(value: TheCragTick) -> Result<Self, Self::Error> {
-        let date = value.ascent_date.map(|d: DateTime<Utc>| d.date_naive());
-        let route_name = Some(value.route_name);
-        let route_location = Some(value.crag_path);
-        let route_discipline = Some(Discipline::from(value.route_gear_style));
-        let ascent_discipline = Some(Discipline::from(value.ascent_gear_style));
-        let route_grade = Some(value.route_grade);
-        let ascent_grade = Some(value.ascent_grade);
-        let comment = Some(value.comment);
+impl From<NaiveDate> for PartialDate {
+    fn from(value: NaiveDate) -> Self {
+        PartialDate::Full(value)
+    }
+}
 
-        Ok(OpenTick {
-            date,
-            route_name,
-            route_location,
-            route_discipline,
-            ascent_discipline,
-            route_grade,
-            ascent_grade,
-            comment,
-        })
+#[cfg(feature = "serde")]
+impl serde::Serialize for PartialDate {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
     }
 }
 
-/// Errors in conversion of ticks
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PartialDate {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <std::borrow::Cow<str>>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Errors in parsing a [`PartialDate`]
 #[non_exhaustive]
-#[derive(Debug)]
-pub enum ConversionError {}
+#[derive(Debug, Clone, PartialEq)]
+pub enum PartialDateParseError {
+    /// The text isn't a date, year and month, or year
+    Unrecognized(String),
+}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl fmt::Display for PartialDateParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PartialDateParseError::Unrecognized(s) => write!(f, "unrecognized date: {s:?}"),
+        }
+    }
+}
 
-    #[test]
+impl std::error::Error for PartialDateParseError {}
+
+/// Location of a route
+///
+/// Built from as much as the source platform records; fields the source doesn't provide are
+/// `None`.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Location {
+    /// Country containing the route
+    pub country: Option<String>,
+    /// Region within the country, e.g. a state
+    pub region: Option<String>,
+    /// Most specific area containing the route
+    pub crag: Option<String>,
+    /// Hierarchy of areas containing the route, from broadest to most specific
+    pub full_path: Vec<String>,
+}
+
+impl Location {
+    /// Location from a hierarchy of areas, from broadest to most specific
+    ///
+    /// The first area is taken to be the country, the second the region, and the last the crag.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::Location;
+    ///
+    /// let l = Location::from_path("Australia > Victoria > Arapiles");
+    ///
+    /// assert_eq!(l.country.as_deref(), Some("Australia"));
+    /// assert_eq!(l.region.as_deref(), Some("Victoria"));
+    /// assert_eq!(l.crag.as_deref(), Some("Arapiles"));
+    /// ```
+    pub fn from_path(path: &str) -> Location {
+        let full_path = util::parse_area_path(path);
+
+        Location {
+            country: full_path.first().cloned(),
+            region: Self::region_of(&full_path),
+            crag: Self::crag_of(&full_path),
+            full_path,
+        }
+    }
+
+    /// Location from a Mountain Project area path
+    ///
+    /// Mountain Project's top-level areas are US states, and `International` for everything
+    /// else.  Paths under a state are in the US, with the state as the region.  International
+    /// paths mix continents and countries at their upper levels, so only their crag is taken.
+    #[cfg(feature = "serde")]
+    fn from_mountain_project_path(path: &str) -> Location {
+        let full_path = util::parse_area_path(path);
+
+        let (country, region) = match full_path.first().map(String::as_str) {
+            None | Some("International") => (None, None),
+            Some(state) => (Some("United States".to_string()), Some(state.to_string())),
+        };
+
+        Location {
+            country,
+            region,
+            crag: Self::crag_of(&full_path),
+            full_path,
+        }
+    }
+
+    fn region_of(full_path: &[String]) -> Option<String> {
+        match full_path {
+            [_, region, _, ..] => Some(region.clone()),
+            _ => None,
+        }
+    }
+
+    fn crag_of(full_path: &[String]) -> Option<String> {
+        match full_path {
+            [_, .., crag] => Some(crag.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// A tick as recorded by its source platform
+#[cfg(feature = "raw")]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub enum RawTick {
+    MountainProject(Box<MountainProjectTick>),
+    TheCrag(Box<TheCragTick>),
+    VerticalLife(Box<VerticalLifeTick>),
+}
+
+/// Platforms that ticks can be imported from
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Source {
+    /// <https://www.mountainproject.com>
+    MountainProject,
+    /// <https://www.thecrag.com>
+    TheCrag,
+    /// <https://www.vertical-life.info>
+    VerticalLife,
+}
+
+/// ID of a route in a platform's database
+#[cfg(feature = "serde")]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RouteRef {
+    MountainProject(MountainProjectRouteId),
+    TheCrag(TheCragRouteId),
+}
+
+/// A training board, such as a MoonBoard or Kilter Board
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TrainingBoard {
+    /// Name of the board, e.g. `MoonBoard 2016`
+    pub name: String,
+    /// Angle of the board, in degrees past vertical
+    pub angle: Option<u8>,
+}
+
+/// Styles of ascent
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum AscentStyle {
+    /// Clean lead on the first try, with no prior knowledge of the route
+    Onsight,
+    /// Clean lead or boulder on the first try, with prior knowledge of the route
+    Flash,
+    /// Clean lead after previous attempts, placing gear or clipping on lead
+    Redpoint,
+    /// Clean lead after previous attempts, with gear or draws pre-placed
+    Pinkpoint,
+    /// Clean lead of a bolted route, placing gear instead of clipping the bolts
+    Greenpoint,
+    /// Clean ascent, without further detail; e.g. a boulder send
+    Send,
+    /// Clean ascent of a route already sent
+    Repeat,
+    /// Lead, without further detail
+    Lead,
+    /// Lead with falls or rests on the rope
+    Hangdog,
+    /// Top rope ascent
+    TopRope,
+    /// Following or seconding a lead climber
+    Second,
+    /// Solo ascent, with or without a rope
+    Solo,
+    /// Aided ascent
+    Aid,
+    /// Attempt without reaching the top
+    Attempt,
+    /// Boulder ascent touching the ground, a pad or a spotter on the way
+    Dab,
+    /// Ascent logged on theCrag as a ghost, which doesn't count as climbed
+    Ghost,
+    /// Style couldn't be determined
+    Unknown,
+}
+
+impl AscentStyle {
+    /// Whether this style counts as a send; a clean lead, boulder or solo ascent
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::AscentStyle;
+    ///
+    /// assert!(AscentStyle::Redpoint.is_send());
+    /// assert!(!AscentStyle::Attempt.is_send());
+    /// ```
+    pub fn is_send(&self) -> bool {
+        matches!(
+            self,
+            AscentStyle::Onsight
+                | AscentStyle::Flash
+                | AscentStyle::Redpoint
+                | AscentStyle::Pinkpoint
+                | AscentStyle::Greenpoint
+                | AscentStyle::Send
+                | AscentStyle::Repeat
+                | AscentStyle::Solo
+        )
+    }
+
+    /// What this style says about the ascent
+    ///
+    /// Styles that don't say whether the climb was sent, such as [`AscentStyle::TopRope`], are
+    /// [`Outcome::Unknown`].
+    pub fn outcome(&self) -> Outcome {
+        match self {
+            AscentStyle::Repeat => Outcome::Repeat,
+            AscentStyle::Attempt | AscentStyle::Dab | AscentStyle::Hangdog => Outcome::Attempt,
+            style if style.is_send() => Outcome::Send,
+            _ => Outcome::Unknown,
+        }
+    }
+}
+
+/// Lowercase name, e.g. `top rope`
+impl fmt::Display for AscentStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            AscentStyle::Onsight => "onsight",
+            AscentStyle::Flash => "flash",
+            AscentStyle::Redpoint => "redpoint",
+            AscentStyle::Pinkpoint => "pinkpoint",
+            AscentStyle::Greenpoint => "greenpoint",
+            AscentStyle::Send => "send",
+            AscentStyle::Repeat => "repeat",
+            AscentStyle::Lead => "lead",
+            AscentStyle::Hangdog => "hangdog",
+            AscentStyle::TopRope => "top rope",
+            AscentStyle::Second => "second",
+            AscentStyle::Solo => "solo",
+            AscentStyle::Aid => "aid",
+            AscentStyle::Attempt => "attempt",
+            AscentStyle::Dab => "dab",
+            AscentStyle::Ghost => "ghost",
+            AscentStyle::Unknown => "unknown style",
+        })
+    }
+}
+
+/// Result of an ascent
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Outcome {
+    /// A first clean ascent
+    Send,
+    /// Didn't climb the route cleanly
+    Attempt,
+    /// A clean ascent of a route already sent
+    Repeat,
+    /// Couldn't be determined
+    Unknown,
+}
+
+#[cfg(feature = "serde")]
+impl From<(MountainProjectStyle, Option<MountainProjectLeadStyle>)> for AscentStyle {
+    fn from(value: (MountainProjectStyle, Option<MountainProjectLeadStyle>)) -> Self {
+        match value {
+            (MountainProjectStyle::Attempt, _) => AscentStyle::Attempt,
+            (MountainProjectStyle::Flash, _) => AscentStyle::Flash,
+            (MountainProjectStyle::Follow, _) => AscentStyle::Second,
+            (MountainProjectStyle::Lead, Some(MountainProjectLeadStyle::FellHung)) => {
+                AscentStyle::Hangdog
+            }
+            (MountainProjectStyle::Lead, Some(MountainProjectLeadStyle::Flash)) => {
+                AscentStyle::Flash
+            }
+            (MountainProjectStyle::Lead, Some(MountainProjectLeadStyle::Onsight)) => {
+                AscentStyle::Onsight
+            }
+            (MountainProjectStyle::Lead, Some(MountainProjectLeadStyle::Pinkpoint)) => {
+                AscentStyle::Pinkpoint
+            }
+            (MountainProjectStyle::Lead, Some(MountainProjectLeadStyle::Redpoint)) => {
+                AscentStyle::Redpoint
+            }
+            (MountainProjectStyle::Lead, None | Some(MountainProjectLeadStyle::Other(_))) => {
+                AscentStyle::Lead
+            }
+            (MountainProjectStyle::Send, _) => AscentStyle::Send,
+            (MountainProjectStyle::Solo, _) => AscentStyle::Solo,
+            (MountainProjectStyle::TR, _) => AscentStyle::TopRope,
+            (MountainProjectStyle::Other(_), _) => AscentStyle::Unknown,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<TheCragAscentType> for AscentStyle {
+    fn from(value: TheCragAscentType) -> Self {
+        match value {
+            TheCragAscentType::Aid | TheCragAscentType::AidSolo => AscentStyle::Aid,
+            TheCragAscentType::Attempt | TheCragAscentType::Retreat => AscentStyle::Attempt,
+            TheCragAscentType::Dab => AscentStyle::Dab,
+            TheCragAscentType::Clean | TheCragAscentType::Send | TheCragAscentType::Tick => {
+                AscentStyle::Send
+            }
+            TheCragAscentType::Flash => AscentStyle::Flash,
+            TheCragAscentType::Onsight | TheCragAscentType::GreenPointOnsight => {
+                AscentStyle::Onsight
+            }
+            TheCragAscentType::Greenpoint => AscentStyle::Greenpoint,
+            TheCragAscentType::GroundUpRedPoint | TheCragAscentType::RedPoint => {
+                AscentStyle::Redpoint
+            }
+            TheCragAscentType::HangDog | TheCragAscentType::Working => AscentStyle::Hangdog,
+            TheCragAscentType::LeadSolo | TheCragAscentType::RopedSolo => AscentStyle::Solo,
+            TheCragAscentType::PinkPoint => AscentStyle::Pinkpoint,
+            TheCragAscentType::Repeat => AscentStyle::Repeat,
+            TheCragAscentType::SecondClean | TheCragAscentType::SecondWithRest => {
+                AscentStyle::Second
+            }
+            TheCragAscentType::TopRope
+            | TheCragAscentType::TopRopeClean
+            | TheCragAscentType::TopRopeFlash
+            | TheCragAscentType::TopRopeOnsight
+            | TheCragAscentType::TopRopeWithRest => AscentStyle::TopRope,
+            TheCragAscentType::Ghost => AscentStyle::Ghost,
+            TheCragAscentType::Mark | TheCragAscentType::Other(_) => AscentStyle::Unknown,
+        }
+    }
+}
+
+/// Disciplines
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Discipline {
+    aid: bool,
+    alpine: bool,
+    bouldering: bool,
+    deep_water_solo: bool,
+    ice: bool,
+    sport: bool,
+    top_rope: bool,
+    trad: bool,
+    unknown: bool,
+}
+
+/// A single discipline
+///
+/// Disciplines are ordered as declared: alphabetically, with [`DisciplineKind::Unknown`] last.
+/// [`Discipline::iter`] yields them in this order, and maps keyed by discipline, such as from
+/// [`stats::discipline_counts`], are sorted by it, so output is the same from run to run.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum DisciplineKind {
+    Aid,
+    /// Long routes in the mountains, on any mix of rock, snow and ice
+    Alpine,
+    Bouldering,
+    DeepWaterSolo,
+    Ice,
+    Sport,
+    TopRope,
+    Trad,
+    /// The source didn't say
+    Unknown,
+}
+
+impl Discipline {
+    /// The disciplines that are set
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::Discipline;
+    ///
+    /// assert_eq!(Discipline::default().iter().count(), 0);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = DisciplineKind> {
+        [
+            (self.aid, DisciplineKind::Aid),
+            (self.alpine, DisciplineKind::Alpine),
+            (self.bouldering, DisciplineKind::Bouldering),
+            (self.deep_water_solo, DisciplineKind::DeepWaterSolo),
+            (self.ice, DisciplineKind::Ice),
+            (self.sport, DisciplineKind::Sport),
+            (self.top_rope, DisciplineKind::TopRope),
+            (self.trad, DisciplineKind::Trad),
+            (self.unknown, DisciplineKind::Unknown),
+        ]
+        .into_iter()
+        .filter_map(|(set, kind)| set.then_some(kind))
+    }
+
+    /// The discipline that best describes a climb in several, `None` if none is set
+    ///
+    /// Disciplines that ask more of the climber take precedence: alpine, then ice, aid, trad,
+    /// sport, top rope, deep water solo and bouldering.  So a route that is both sport and trad is
+    /// trad, since it needs a rack.  Unknown is primary only if nothing else is set.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::{Discipline, DisciplineKind};
+    ///
+    /// let discipline: Discipline = "sport, trad".parse().unwrap();
+    /// assert_eq!(discipline.primary(), Some(DisciplineKind::Trad));
+    /// assert_eq!(Discipline::default().primary(), None);
+    /// ```
+    pub fn primary(&self) -> Option<DisciplineKind> {
+        [
+            DisciplineKind::Alpine,
+            DisciplineKind::Ice,
+            DisciplineKind::Aid,
+            DisciplineKind::Trad,
+            DisciplineKind::Sport,
+            DisciplineKind::TopRope,
+            DisciplineKind::DeepWaterSolo,
+            DisciplineKind::Bouldering,
+            DisciplineKind::Unknown,
+        ]
+        .into_iter()
+        .find(|&kind| self.contains(kind))
+    }
+
+    /// The disciplines with the given kinds set, the inverse of [`Discipline::iter`]
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::{Discipline, DisciplineKind};
+    ///
+    /// let discipline = Discipline::from_kinds(&[DisciplineKind::Sport]);
+    /// assert_eq!(discipline, "sport".parse().unwrap());
+    /// ```
+    pub fn from_kinds(kinds: &[DisciplineKind]) -> Discipline {
+        let mut discipline = Discipline::default();
+        for &kind in kinds {
+            discipline.set(kind);
+        }
+        discipline
+    }
+
+    /// Whether the given discipline is set
+    pub fn contains(&self, kind: DisciplineKind) -> bool {
+        self.iter().any(|k| k == kind)
+    }
+
+    /// Whether two sets of known disciplines have none in common
+    ///
+    /// Such as a trad route climbed as a sport route, using fixed gear.  Unknown disciplines are
+    /// ignored, and an empty set conflicts with nothing.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::Discipline;
+    ///
+    /// let trad: Discipline = "trad".parse().unwrap();
+    /// assert!(trad.conflicts_with(&"sport".parse().unwrap()));
+    /// assert!(!trad.conflicts_with(&"sport, trad".parse().unwrap()));
+    /// ```
+    pub fn conflicts_with(&self, other: &Discipline) -> bool {
+        let known = |d: &Discipline| {
+            d.iter()
+                .filter(|&k| k != DisciplineKind::Unknown)
+                .collect::<Vec<_>>()
+        };
+        let (ours, theirs) = (known(self), known(other));
+
+        !ours.is_empty() && !theirs.is_empty() && !ours.iter().any(|k| theirs.contains(k))
+    }
+
+    /// Parse a comma separated list of disciplines, rejecting any that aren't recognized
+    ///
+    /// Accepts the same names as the lenient [`FromStr`] implementation.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::{Discipline, DisciplineKind};
+    ///
+    /// let discipline = Discipline::try_from_str("Sport, TR").unwrap();
+    /// assert!(discipline.contains(DisciplineKind::TopRope));
+    ///
+    /// assert!(Discipline::try_from_str("sprot").is_err());
+    /// ```
+    pub fn try_from_str(s: &str) -> Result<Discipline, DisciplineParseError> {
+        let mut discipline = Discipline::default();
+
+        for token in s.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            let kind = DisciplineKind::from_token(token)
+                .ok_or_else(|| DisciplineParseError::UnrecognizedToken(token.to_string()))?;
+            discipline.set(kind);
+        }
+
+        Ok(discipline)
+    }
+
+    /// These disciplines, or those implied by `grade` if none are known
+    ///
+    /// Only grades in a system used by a single discipline imply one, such as the V-scale for
+    /// bouldering and water ice grades for ice.  YDS and French grades are used for several, so
+    /// imply nothing.
+    #[cfg(feature = "serde")]
+    fn or_from_grade(self, grade: &str) -> Discipline {
+        if self.iter().any(|k| k != DisciplineKind::Unknown) {
+            return self;
+        }
+
+        match recognized_grade(grade).and_then(|g| g.system()) {
+            Some(system) if system.applies_to().len() == 1 => {
+                Discipline::from_kinds(system.applies_to())
+            }
+            _ => self,
+        }
+    }
+
+    fn set(&mut self, kind: DisciplineKind) {
+        let flag = match kind {
+            DisciplineKind::Aid => &mut self.aid,
+            DisciplineKind::Alpine => &mut self.alpine,
+            DisciplineKind::Bouldering => &mut self.bouldering,
+            DisciplineKind::DeepWaterSolo => &mut self.deep_water_solo,
+            DisciplineKind::Ice => &mut self.ice,
+            DisciplineKind::Sport => &mut self.sport,
+            DisciplineKind::TopRope => &mut self.top_rope,
+            DisciplineKind::Trad => &mut self.trad,
+            DisciplineKind::Unknown => &mut self.unknown,
+        };
+        *flag = true;
+    }
+}
+
+/// Lowercase name, e.g. `deep water solo`
+impl fmt::Display for DisciplineKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            DisciplineKind::Aid => "aid",
+            DisciplineKind::Alpine => "alpine",
+            DisciplineKind::Bouldering => "bouldering",
+            DisciplineKind::DeepWaterSolo => "deep water solo",
+            DisciplineKind::Ice => "ice",
+            DisciplineKind::Sport => "sport",
+            DisciplineKind::TopRope => "top rope",
+            DisciplineKind::Trad => "trad",
+            DisciplineKind::Unknown => "unknown discipline",
+        })
+    }
+}
+
+impl DisciplineKind {
+    /// Discipline named by `token`, ignoring case and separators
+    fn from_token(token: &str) -> Option<DisciplineKind> {
+        let token: String = token
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect();
+
+        match token.as_str() {
+            "aid" => Some(DisciplineKind::Aid),
+            "alpine" => Some(DisciplineKind::Alpine),
+            "boulder" | "bouldering" => Some(DisciplineKind::Bouldering),
+            "dws" | "deepwatersolo" => Some(DisciplineKind::DeepWaterSolo),
+            "ice" => Some(DisciplineKind::Ice),
+            "sport" => Some(DisciplineKind::Sport),
+            "tr" | "toprope" => Some(DisciplineKind::TopRope),
+            "trad" => Some(DisciplineKind::Trad),
+            "unknown" => Some(DisciplineKind::Unknown),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a comma separated list of disciplines, e.g. `Sport, TR`
+///
+/// Names are matched ignoring case, spaces and underscores, and common abbreviations such as `TR`
+/// and `DWS` are accepted.  Unrecognized names set [`DisciplineKind::Unknown`]; use
+/// [`Discipline::try_from_str`] to reject them instead.
+impl FromStr for Discipline {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut discipline = Discipline::default();
+
+        for token in s.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            discipline.set(DisciplineKind::from_token(token).unwrap_or(DisciplineKind::Unknown));
+        }
+
+        Ok(discipline)
+    }
+}
+
+impl TryFrom<&str> for Discipline {
+    type Error = DisciplineParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Discipline::try_from_str(value)
+    }
+}
+
+/// Errors in strictly parsing a [`Discipline`]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub enum DisciplineParseError {
+    /// A name that isn't a known discipline
+    UnrecognizedToken(String),
+}
+
+impl fmt::Display for DisciplineParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DisciplineParseError::UnrecognizedToken(t) => {
+                write!(f, "unrecognized discipline: {t:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DisciplineParseError {}
+
+#[cfg(feature = "serde")]
+impl From<MountainProjectRouteType> for Discipline {
+    fn from(value: MountainProjectRouteType) -> Self {
+        Discipline {
+            aid: value.aid,
+            alpine: value.alpine,
+            bouldering: value.boulder,
+            deep_water_solo: false, // TODO
+            // There is no mixed discipline, and mixed grades are counted as ice grades
+            ice: value.ice || value.mixed,
+            sport: value.sport,
+            trad: value.trad,
+            top_rope: value.top_rope,
+            unknown: value.unknown,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<TheCragGearStyle> for Discipline {
+    fn from(value: TheCragGearStyle) -> Self {
+        match value {
+            TheCragGearStyle::Aid => Discipline {
+                aid: true,
+                ..Default::default()
+            },
+            TheCragGearStyle::Alpine => Discipline {
+                alpine: true,
+                ..Default::default()
+            },
+            TheCragGearStyle::Boulder => Discipline {
+                bouldering: true,
+                ..Default::default()
+            },
+            TheCragGearStyle::Sport => Discipline {
+                sport: true,
+                ..Default::default()
+            },
+            TheCragGearStyle::TopRope => Discipline {
+                top_rope: true,
+                ..Default::default()
+            },
+            TheCragGearStyle::Trad => Discipline {
+                trad: true,
+                ..Default::default()
+            },
+            TheCragGearStyle::Unknown => Discipline {
+                unknown: true,
+                ..Default::default()
+            },
+            _ => Discipline {
+                ..Default::default()
+            },
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<VerticalLifeType> for Discipline {
+    fn from(value: VerticalLifeType) -> Self {
+        match value {
+            VerticalLifeType::Boulder => Discipline {
+                bouldering: true,
+                ..Default::default()
+            },
+            VerticalLifeType::Sport => Discipline {
+                sport: true,
+                ..Default::default()
+            },
+            VerticalLifeType::TopRope => Discipline {
+                top_rope: true,
+                ..Default::default()
+            },
+            VerticalLifeType::Other(_) => Discipline {
+                unknown: true,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<VerticalLifeStyle> for AscentStyle {
+    fn from(value: VerticalLifeStyle) -> Self {
+        match value {
+            VerticalLifeStyle::Onsight => AscentStyle::Onsight,
+            VerticalLifeStyle::Flash => AscentStyle::Flash,
+            VerticalLifeStyle::Redpoint => AscentStyle::Redpoint,
+            VerticalLifeStyle::Top => AscentStyle::Send,
+            VerticalLifeStyle::Attempt => AscentStyle::Attempt,
+            VerticalLifeStyle::Other(_) => AscentStyle::Unknown,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<MountainProjectTick> for OpenTick {
+    type Error = ConversionError;
+
+    fn try_from(value: MountainProjectTick) -> Result<Self, Self::Error> {
+        #[cfg(feature = "raw")]
+        let raw = Some(RawTick::MountainProject(Box::new(value.clone())));
+
+        let date = value.date;
+        let route_name = Some(value.route);
+        let location = Some(Location::from_mountain_project_path(&value.location));
+        let route_location = Some(value.location);
+        let route_url = value.url.map(String::from);
+        let route_discipline =
+            Some(Discipline::from(value.route_type).or_from_grade(&value.rating));
+        let ascent_discipline = None;
+        let route_grade = Some(value.rating);
+        let ascent_grade = Some(value.your_rating).filter(|r| !r.is_empty());
+        let route_stars = value.avg_stars.filter(|&s| s >= 0.0);
+        let ascent_style = Some(AscentStyle::from((value.style, value.lead_style)));
+        let attempts = util::parse_attempts_tag(&value.notes);
+        let pitches = value.pitches;
+        let comment = Some(value.notes);
+        let source = Some(Source::MountainProject);
+
+        Ok(OpenTick {
+            date,
+            partial_date: None,
+            route_name,
+            route_location,
+            location,
+            route_url,
+            coordinates: None,
+            route_discipline,
+            ascent_discipline,
+            route_grade,
+            ascent_grade,
+            route_stars,
+            route_quality: None,
+            ascent_style,
+            comment,
+            partners: None,
+            attempts,
+            pitches,
+            indoor: None,
+            board: None,
+            source,
+            #[cfg(feature = "raw")]
+            raw,
+            #[cfg(feature = "provenance")]
+            provenance: HashMap::new(),
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<TheCragTick> for OpenTick {
+    type Error = ConversionError;
+
+    fn try_from(value: TheCragTick) -> Result<Self, Self::Error> {
+        #[cfg(feature = "raw")]
+        let raw = Some(RawTick::TheCrag(Box::new(value.clone())));
+
+        let date = value.local_ascent_date(&Utc);
+        let route_stars = value.route_stars_count().map(f32::from);
+        let route_quality = value.quality_percent();
+        // Older exports may leave columns blank that the label covers
+        let label = (value.route_name.is_empty()
+            || value.route_grade.is_empty()
+            || value.ascent_type.as_other() == Some(""))
+        .then(|| value.parsed_ascent_label())
+        .flatten();
+        let route_name = match (value.route_name.is_empty(), &label) {
+            (true, Some(label)) => Some(label.route_name.clone()),
+            _ => Some(value.route_name),
+        };
+        // theCrag records the country and crag separately, so only the region needs parsing
+        let full_path = util::parse_area_path(&value.crag_path);
+        let location = Some(Location {
+            country: Some(value.country).filter(|c| !c.is_empty()),
+            region: Location::region_of(&full_path),
+            crag: Some(value.crag_name).filter(|c| !c.is_empty()),
+            full_path,
+        });
+        let route_location = Some(value.crag_path);
+        let route_url = value
+            .route_link
+            .and_then(|link| link.url().map(|url| url.to_string()))
+            .or_else(|| {
+                Some(format!(
+                    "https://www.thecrag.com/route/{}",
+                    value.route_id?.0
+                ))
+            });
+        let route_grade = match (value.route_grade.is_empty(), &label) {
+            (true, Some(label)) => label.grade.clone(),
+            _ => Some(value.route_grade),
+        };
+        let route_discipline = Some(
+            Discipline::from(value.route_gear_style)
+                .or_from_grade(route_grade.as_deref().unwrap_or_default()),
+        );
+        let ascent_discipline = Some(Discipline::from(value.ascent_gear_style));
+        let ascent_grade = Some(value.ascent_grade).filter(|g| !g.is_empty());
+        let ascent_type = match (value.ascent_type.as_other(), label) {
+            (Some(""), Some(label)) => label.ascent_type.unwrap_or(value.ascent_type),
+            _ => value.ascent_type,
+        };
+        let ascent_style = Some(AscentStyle::from(ascent_type));
+        // Attempts on the same day may be logged together, with a count
+        let attempts = util::parse_attempts_tag(&value.comment).or_else(|| {
+            (ascent_style.map(|s| s.outcome()) == Some(Outcome::Attempt))
+                .then(|| u32::try_from(value.number_ascents).ok())
+                .flatten()
+        });
+        let comment = Some(value.comment);
+        let partners: Vec<String> = value
+            .with
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(String::from)
+            .collect();
+        let partners = Some(partners).filter(|p| !p.is_empty());
+        let source = Some(Source::TheCrag);
+
+        Ok(OpenTick {
+            date,
+            partial_date: None,
+            route_name,
+            route_location,
+            location,
+            route_url,
+            coordinates: None,
+            route_discipline,
+            ascent_discipline,
+            route_grade,
+            ascent_grade,
+            route_stars,
+            route_quality,
+            ascent_style,
+            comment,
+            partners,
+            attempts,
+            pitches: None,
+            indoor: None,
+            board: None,
+            source,
+            #[cfg(feature = "raw")]
+            raw,
+            #[cfg(feature = "provenance")]
+            provenance: HashMap::new(),
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<VerticalLifeTick> for OpenTick {
+    type Error = ConversionError;
+
+    fn try_from(value: VerticalLifeTick) -> Result<Self, Self::Error> {
+        #[cfg(feature = "raw")]
+        let raw = Some(RawTick::VerticalLife(Box::new(value.clone())));
+
+        // Gym climbs on a board often have no name of their own
+        let route_name = Some(value.name).filter(|n| !n.is_empty());
+        // Gyms are recorded by name alone, which is taken as the crag rather than the country
+        let full_path = util::parse_area_path(&value.location);
+        let location = Some(match full_path.as_slice() {
+            [gym] => Location {
+                crag: Some(gym.clone()),
+                full_path,
+                ..Default::default()
+            },
+            _ => Location::from_path(&value.location),
+        });
+        let board = Some(value.board)
+            .filter(|b| !b.is_empty())
+            .map(|name| TrainingBoard {
+                name,
+                angle: value.board_angle,
+            });
+
+        Ok(OpenTick {
+            date: value.date.and_then(|d| d.full()),
+            partial_date: value.date.filter(|d| d.full().is_none()),
+            route_name,
+            route_location: Some(value.location),
+            location,
+            route_url: None,
+            coordinates: None,
+            route_discipline: Some(Discipline::from(value.climb_type).or_from_grade(&value.grade)),
+            ascent_discipline: None,
+            route_grade: Some(value.grade),
+            ascent_grade: None,
+            route_stars: None,
+            route_quality: None,
+            ascent_style: Some(AscentStyle::from(value.style)),
+            attempts: value
+                .tries
+                .or_else(|| util::parse_attempts_tag(&value.notes)),
+            comment: Some(value.notes),
+            partners: None,
+            pitches: None,
+            indoor: value.indoor,
+            board,
+            source: Some(Source::VerticalLife),
+            #[cfg(feature = "raw")]
+            raw,
+            #[cfg(feature = "provenance")]
+            provenance: HashMap::new(),
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<&Discipline> for TheCragGearStyle {
+    fn from(value: &Discipline) -> Self {
+        if value.trad {
+            TheCragGearStyle::Trad
+        } else if value.sport {
+            TheCragGearStyle::Sport
+        } else if value.top_rope {
+            TheCragGearStyle::TopRope
+        } else if value.bouldering {
+            TheCragGearStyle::Boulder
+        } else if value.aid {
+            TheCragGearStyle::Aid
+        } else if value.alpine {
+            TheCragGearStyle::Alpine
+        } else if value.unknown {
+            TheCragGearStyle::Unknown
+        } else {
+            TheCragGearStyle::None
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<AscentStyle> for TheCragAscentType {
+    fn from(value: AscentStyle) -> Self {
+        match value {
+            AscentStyle::Onsight => TheCragAscentType::Onsight,
+            AscentStyle::Flash => TheCragAscentType::Flash,
+            AscentStyle::Redpoint => TheCragAscentType::RedPoint,
+            AscentStyle::Pinkpoint => TheCragAscentType::PinkPoint,
+            AscentStyle::Greenpoint => TheCragAscentType::Greenpoint,
+            AscentStyle::Send => TheCragAscentType::Send,
+            AscentStyle::Repeat => TheCragAscentType::Repeat,
+            AscentStyle::Hangdog => TheCragAscentType::HangDog,
+            AscentStyle::TopRope => TheCragAscentType::TopRope,
+            AscentStyle::Second => TheCragAscentType::SecondClean,
+            AscentStyle::Aid => TheCragAscentType::Aid,
+            AscentStyle::Attempt => TheCragAscentType::Attempt,
+            AscentStyle::Dab => TheCragAscentType::Dab,
+            AscentStyle::Ghost => TheCragAscentType::Ghost,
+            // theCrag's generic ascent type
+            AscentStyle::Lead | AscentStyle::Solo | AscentStyle::Unknown => TheCragAscentType::Tick,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<OpenTick> for TheCragTick {
+    type Error = ConversionError;
+
+    /// Convert to a tick that can be imported to theCrag
+    ///
+    /// Fields that only theCrag can assign, such as IDs and links, are left empty.  Star
+    /// ratings are only kept for ticks from theCrag, since other platforms rate on other scales,
+    /// and a partial date is dropped, since theCrag records only full dates.  The country and
+    /// crag are taken from the parsed [`OpenTick::location`] where known, otherwise from the
+    /// first and last areas of the route's location.
+    fn try_from(value: OpenTick) -> Result<Self, Self::Error> {
+        let route_name = value
+            .route_name
+            .ok_or(ConversionError::MissingField("route_name"))?;
+        let location = value.location.unwrap_or_default();
+        let crag_path = value
+            .route_location
+            .unwrap_or_else(|| location.full_path.join(" > "));
+        let country = location.country.unwrap_or_else(|| {
+            crag_path
+                .split(" > ")
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        });
+        let crag_name = location.crag.unwrap_or_else(|| {
+            crag_path
+                .rsplit(" > ")
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        });
+        let route_stars = match value.source {
+            Some(Source::TheCrag) => value.route_stars.map(|s| format!("{s:.0}")),
+            _ => None,
+        };
+        let gear_style = |d: &Option<Discipline>| {
+            d.as_ref()
+                .map(TheCragGearStyle::from)
+                .unwrap_or(TheCragGearStyle::None)
+        };
+
+        Ok(TheCragTick {
+            route_name,
+            ascent_label: String::new(),
+            ascent_id: None,
+            ascent_link: None,
+            ascent_type: value
+                .ascent_style
+                .map(TheCragAscentType::from)
+                .unwrap_or(TheCragAscentType::Tick),
+            route_grade: value.route_grade.unwrap_or_default(),
+            ascent_grade: value.ascent_grade.unwrap_or_default(),
+            route_gear_style: gear_style(&value.route_discipline),
+            ascent_gear_style: gear_style(&value.ascent_discipline),
+            route_height: String::new(),
+            ascent_height: String::new(),
+            number_ascents: 1,
+            route_stars: route_stars.unwrap_or_default(),
+            route_id: None,
+            route_link: None,
+            country,
+            country_link: None,
+            crag_name,
+            crag_link: None,
+            crag_path,
+            with: value.partners.map(|p| p.join(", ")).unwrap_or_default(),
+            comment: value.comment.unwrap_or_default(),
+            quality: value
+                .route_quality
+                .map(|q| format!("{q}%"))
+                .unwrap_or_default(),
+            ascent_date: value.date.map(|d| d.and_time(NaiveTime::MIN).and_utc()),
+            log_date: None,
+            shot: None,
+        })
+    }
+}
+
+/// Ticks that can be converted to an [`OpenTick`]
+///
+/// Implemented by the tick type of every supported platform, so that code can accept ticks from
+/// any source.
+///
+/// # Examples
+/// ```
+/// use open_tick::{IntoOpenTick, OpenTick};
+///
+/// fn import<T: IntoOpenTick>(ticks: Vec<T>) -> Vec<OpenTick> {
+///     ticks
+///         .into_iter()
+///         .filter_map(|t| t.into_open_tick().ok())
+///         .collect()
+/// }
+/// ```
+pub trait IntoOpenTick: Sized {
+    fn into_open_tick(self) -> Result<OpenTick, ConversionError>;
+
+    /// Convert, listing the populated fields of the source record that [`OpenTick`] has no place
+    /// for
+    ///
+    /// The default implementation reports nothing dropped.
+    fn into_converted_tick(self) -> Result<ConvertedTick, ConversionError> {
+        Ok(ConvertedTick::new(
+            self.into_open_tick()?,
+            Vec::new(),
+            Vec::new(),
+        ))
+    }
+}
+
+/// A converted tick, with the fields lost in conversion and anything else worth the user's
+/// attention
+///
+/// Returned by [`IntoOpenTick::into_converted_tick`].
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct ConvertedTick {
+    pub tick: OpenTick,
+    /// Names of the source record's fields that were populated but not converted
+    pub dropped: Vec<&'static str>,
+    /// Problems with the source record that didn't stop the conversion
+    pub warnings: Vec<String>,
+    /// How completely the source record converted, from 0 to 1
+    ///
+    /// The fraction of these that are known: the date, route name, location, grade in a
+    /// recognized system, discipline and ascent style.  Conversions with a low confidence may be
+    /// worth reviewing by hand.
+    pub confidence: f32,
+}
+
+impl ConvertedTick {
+    /// Wrap a converted tick, scoring its [`ConvertedTick::confidence`]
+    fn new(tick: OpenTick, dropped: Vec<&'static str>, warnings: Vec<String>) -> ConvertedTick {
+        let known = |d: &Discipline| d.iter().any(|k| k != DisciplineKind::Unknown);
+        let checks = [
+            tick.date.is_some() || tick.partial_date.is_some(),
+            tick.route_name.as_deref().is_some_and(|n| !n.is_empty()),
+            tick.normalized_crag().is_some(),
+            matches!(tick.grade_parse_status(), GradeParseStatus::Parsed(_)),
+            [&tick.route_discipline, &tick.ascent_discipline]
+                .into_iter()
+                .flatten()
+                .any(known),
+            tick.ascent_style.is_some_and(|s| s != AscentStyle::Unknown),
+        ];
+        let confidence = checks.iter().filter(|&&c| c).count() as f32 / checks.len() as f32;
+
+        ConvertedTick {
+            tick,
+            dropped,
+            warnings,
+            confidence,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl IntoOpenTick for MountainProjectTick {
+    fn into_open_tick(self) -> Result<OpenTick, ConversionError> {
+        OpenTick::try_from(self)
+    }
+
+    fn into_converted_tick(self) -> Result<ConvertedTick, ConversionError> {
+        let dropped = self.dropped_fields();
+
+        let mut warnings = Vec::new();
+        if matches!(self.style, MountainProjectStyle::Other(_))
+            || matches!(self.lead_style, Some(MountainProjectLeadStyle::Other(_)))
+        {
+            warnings.push(format!(
+                "unrecognized ascent style {:?}",
+                self.describe_ascent()
+            ));
+        }
+
+        Ok(ConvertedTick::new(
+            OpenTick::try_from(self)?,
+            dropped,
+            warnings,
+        ))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl IntoOpenTick for mountain_project::MountainProjectApiTick {
+    fn into_open_tick(self) -> Result<OpenTick, ConversionError> {
+        MountainProjectTick::from(self).into_open_tick()
+    }
+
+    fn into_converted_tick(self) -> Result<ConvertedTick, ConversionError> {
+        let tick_id = self.tick_id.is_some();
+        let mut converted = MountainProjectTick::from(self).into_converted_tick()?;
+        if tick_id {
+            converted.dropped.insert(0, "tick_id");
+        }
+
+        Ok(converted)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl IntoOpenTick for TheCragTick {
+    fn into_open_tick(self) -> Result<OpenTick, ConversionError> {
+        OpenTick::try_from(self)
+    }
+
+    fn into_converted_tick(self) -> Result<ConvertedTick, ConversionError> {
+        let dropped = self.dropped_fields();
+        let mut warnings = self.malformed_links();
+        let tick = OpenTick::try_from(self)?;
+
+        if let (Some(route), Some(ascent)) = (&tick.route_discipline, &tick.ascent_discipline) {
+            if route.conflicts_with(ascent) {
+                let names = |d: &Discipline| {
+                    d.iter()
+                        .map(|k| k.to_string())
+                        .collect::<Vec<_>>()
+                        .join("/")
+                };
+                warnings.push(format!(
+                    "{} route climbed as {}",
+                    names(route),
+                    names(ascent)
+                ));
+            }
+        }
+
+        Ok(ConvertedTick::new(tick, dropped, warnings))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl IntoOpenTick for VerticalLifeTick {
+    fn into_open_tick(self) -> Result<OpenTick, ConversionError> {
+        OpenTick::try_from(self)
+    }
+}
+
+impl<T: IntoOpenTick> IntoOpenTick for Box<T> {
+    fn into_open_tick(self) -> Result<OpenTick, ConversionError> {
+        (*self).into_open_tick()
+    }
+
+    fn into_converted_tick(self) -> Result<ConvertedTick, ConversionError> {
+        (*self).into_converted_tick()
+    }
+}
+
+/// Errors in conversion of ticks
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum ConversionError {
+    /// A field required by the target format is missing
+    MissingField(&'static str),
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConversionError::MissingField(field) => {
+                write!(f, "missing `{field}`, which the target format requires")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grade::GradeSystem;
+
+    #[cfg(feature = "serde")]
+    fn mp_tick() -> MountainProjectTick {
+        MountainProjectTick {
+            date: NaiveDate::from_ymd_opt(2020, 1, 1),
+            route: "a route name".to_string(),
+            rating: "V2".to_string(),
+            notes: "".to_string(),
+            url: None,
+            pitches: Some(1),
+            location: "A place > the crag".to_string(),
+            avg_stars: Some(3.2),
+            your_stars: 3,
+            style: mountain_project::MountainProjectStyle::Send,
+            lead_style: None,
+            route_type: "Boulder".into(),
+            your_rating: "".to_string(),
+            length: 10,
+            rating_code: 20008,
+        }
+    }
+
+    #[test]
+    fn regrade() {
+        let tick = OpenTick {
+            date: NaiveDate::from_ymd_opt(2020, 1, 1),
+            route_name: Some("A Route Name".to_string()),
+            route_grade: Some("5.12".to_string()),
+            ascent_grade: Some("5.11d".to_string()),
+            ascent_style: Some(AscentStyle::Redpoint),
+            ..Default::default()
+        };
+
+        let regraded = tick.clone().with_route_grade("5.12a");
+        assert_eq!(regraded.route_grade.as_deref(), Some("5.12a"));
+
+        let mut expected = tick.clone();
+        expected.route_grade = Some("5.12a".to_string());
+        assert_eq!(regraded, expected);
+
+        let regraded = tick.with_ascent_grade("5.12b");
+        assert_eq!(regraded.ascent_grade.as_deref(), Some("5.12b"));
+        assert_eq!(regraded.route_grade.as_deref(), Some("5.12"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn international_mountain_project_location() {
+        let location = Location::from_mountain_project_path(
+            "International > Asia > Thailand > Krabi > Railay > Phra Nang Beach",
+        );
+
+        assert_eq!(location.country, None);
+        assert_eq!(location.region, None);
+        assert_eq!(location.crag.as_deref(), Some("Phra Nang Beach"));
+        assert_eq!(location.full_path.len(), 6);
+    }
+
+    #[test]
+    fn partial_dates_tell_ascents_apart() {
+        let ascent = |year| OpenTick {
+            partial_date: Some(PartialDate::Year(year)),
+            route_name: Some("Route".to_string()),
+            source: Some(Source::VerticalLife),
+            ..Default::default()
+        };
+
+        assert!(ascent(2019).same_ascent(&ascent(2019)));
+        assert!(!ascent(2019).same_ascent(&ascent(2021)));
+        assert!(!ascent(2019).same_ascent(&OpenTick {
+            partial_date: None,
+            ..ascent(2019)
+        }));
+    }
+
+    #[test]
     fn a_tick() {
         let t = OpenTick {
             date: NaiveDate::from_ymd_opt(2020, 1, 1),
@@ -177,9 +2147,671 @@ mod tests {
             }),
             route_grade: Some("C3".to_string()),
             ascent_grade: Some("5.11".to_string()),
+            ascent_style: Some(AscentStyle::Onsight),
+            comment: Some("What a fun route".to_string()),
+            ..Default::default()
+        };
+
+        println!("{t:?}");
+        assert_eq!(
+            t.to_string(),
+            "2020-01-01 A Route Name (5.11, trad) — onsight"
+        );
+    }
+
+    #[test]
+    fn display_sparse_tick() {
+        let t = OpenTick {
+            route_grade: Some("V3".to_string()),
+            ascent_grade: Some("".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(t.to_string(), "Unnamed route (V3)");
+        assert_eq!(OpenTick::default().to_string(), "Unnamed route");
+    }
+
+    #[test]
+    fn identity_ignores_comment() {
+        let a = OpenTick {
+            date: NaiveDate::from_ymd_opt(2023, 6, 1),
+            route_name: Some("The Nose".to_string()),
+            route_location: Some("California > Yosemite > El Capitan".to_string()),
+            comment: Some("Long day".to_string()),
+            source: Some(Source::MountainProject),
+            ..Default::default()
+        };
+        let edited = OpenTick {
+            route_name: Some("Nose, The".to_string()),
+            comment: Some("Very long day".to_string()),
+            ..a.clone()
+        };
+        let other_day = OpenTick {
+            date: NaiveDate::from_ymd_opt(2023, 6, 2),
+            ..a.clone()
+        };
+        let other_source = OpenTick {
+            source: Some(Source::TheCrag),
+            ..a.clone()
+        };
+
+        assert_eq!(a.identity_hash(), edited.identity_hash());
+        assert_ne!(a.identity_hash(), other_day.identity_hash());
+        assert_ne!(a.identity_hash(), other_source.identity_hash());
+        // Stored hashes must stay valid, so pin one
+        assert_eq!(OpenTick::default().identity_hash(), 0x4d25_767f_9dce_13f5);
+    }
+
+    #[test]
+    fn same_ascent_ignores_comment_and_source() {
+        let a = OpenTick {
+            date: NaiveDate::from_ymd_opt(2023, 6, 1),
+            route_name: Some("A Route Name".to_string()),
+            route_location: Some("Colorado > Eldorado Canyon".to_string()),
             comment: Some("What a fun route".to_string()),
+            source: Some(Source::MountainProject),
+            ..Default::default()
+        };
+        let b = OpenTick {
+            route_location: Some("USA > Colorado > Eldorado Canyon".to_string()),
+            comment: Some("Scary".to_string()),
+            source: Some(Source::TheCrag),
+            ..a.clone()
+        };
+        let c = OpenTick {
+            date: NaiveDate::from_ymd_opt(2023, 6, 2),
+            ..a.clone()
+        };
+
+        assert!(a.same_ascent(&b));
+        assert!(!a.same_ascent(&c));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn discipline_from_grade() -> Result<(), ConversionError> {
+        let kinds = |t: &OpenTick| {
+            t.route_discipline
+                .as_ref()
+                .map(|d| d.iter().collect::<Vec<_>>())
+        };
+
+        let boulder = OpenTick::try_from(MountainProjectTick {
+            rating: "V5".to_string(),
+            route_type: "Unknown".into(),
+            ..mp_tick()
+        })?;
+        let ice = OpenTick::try_from(MountainProjectTick {
+            rating: "WI4 M5".to_string(),
+            route_type: "".into(),
+            ..mp_tick()
+        })?;
+        let route = OpenTick::try_from(MountainProjectTick {
+            rating: "5.10a".to_string(),
+            route_type: "Unknown".into(),
+            ..mp_tick()
+        })?;
+
+        assert_eq!(kinds(&boulder), Some(vec![DisciplineKind::Bouldering]));
+        assert_eq!(kinds(&ice), Some(vec![DisciplineKind::Ice]));
+        assert_eq!(kinds(&route), Some(vec![DisciplineKind::Unknown]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn effective_grade_prefers_personal() {
+        let tick = OpenTick {
+            route_grade: Some("7a".to_string()),
+            ascent_grade: Some("7a+".to_string()),
+            ..Default::default()
+        };
+        let blank = OpenTick {
+            ascent_grade: Some(" ".to_string()),
+            ..tick.clone()
+        };
+
+        assert_eq!(tick.effective_grade(), Some("7a+"));
+        assert_eq!(tick.effective_grade_parsed(), Some(Grade::parse("7a+")));
+        assert_eq!(blank.effective_grade(), Some("7a"));
+        assert_eq!(
+            OpenTick {
+                route_grade: None,
+                ..blank
+            }
+            .effective_grade(),
+            None
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn mountain_project_personal_grade() -> Result<(), ConversionError> {
+        let blank = OpenTick::try_from(mp_tick())?;
+        assert_eq!(blank.ascent_grade, None);
+        assert_eq!(blank.effective_grade(), Some("V2"));
+
+        let personal = OpenTick::try_from(MountainProjectTick {
+            your_rating: "V3".to_string(),
+            ..mp_tick()
+        })?;
+        assert_eq!(personal.ascent_grade.as_deref(), Some("V3"));
+        assert_eq!(personal.route_grade.as_deref(), Some("V2"));
+        assert_eq!(personal.effective_grade(), Some("V3"));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn thecrag_export_location_and_stars() -> Result<(), ConversionError> {
+        let tick = OpenTick {
+            route_name: Some("Kachoong".to_string()),
+            route_location: Some("Victoria > Arapiles > Pharos Gully".to_string()),
+            location: Some(Location {
+                country: Some("Australia".to_string()),
+                region: Some("Victoria".to_string()),
+                crag: Some("Arapiles".to_string()),
+                full_path: vec![],
+            }),
+            route_stars: Some(3.0),
+            source: Some(Source::TheCrag),
+            ..Default::default()
+        };
+
+        let exported = TheCragTick::try_from(tick.clone())?;
+        assert_eq!(exported.country, "Australia");
+        assert_eq!(exported.crag_name, "Arapiles");
+        assert_eq!(exported.route_stars, "3");
+
+        let from_mountain_project = TheCragTick::try_from(OpenTick {
+            location: None,
+            source: Some(Source::MountainProject),
+            ..tick
+        })?;
+        assert_eq!(from_mountain_project.country, "Victoria");
+        assert_eq!(from_mountain_project.crag_name, "Pharos Gully");
+        assert_eq!(from_mountain_project.route_stars, "");
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn mountain_project_unrated_route() -> Result<(), ConversionError> {
+        let tick = OpenTick::try_from(MountainProjectTick {
+            avg_stars: Some(-1.0),
+            ..mp_tick()
+        })?;
+
+        assert_eq!(tick.route_stars, None);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn mountain_project_area_path() -> Result<(), ConversionError> {
+        let mp_tick = MountainProjectTick {
+            location: "Area > Sub > Crag".to_string(),
+            ..mp_tick()
+        };
+
+        let t = OpenTick::try_from(mp_tick)?;
+
+        assert_eq!(
+            t.location.map(|l| l.full_path),
+            Some(vec![
+                "Area".to_string(),
+                "Sub".to_string(),
+                "Crag".to_string()
+            ])
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "raw")]
+    #[test]
+    fn converted_tick_keeps_raw() -> Result<(), ConversionError> {
+        let mp_tick = mp_tick();
+
+        let t = OpenTick::try_from(mp_tick.clone())?;
+
+        assert_eq!(t.raw, Some(RawTick::MountainProject(Box::new(mp_tick))));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn into_open_tick_boxed() -> Result<(), ConversionError> {
+        fn route_name<T: IntoOpenTick>(t: T) -> Result<Option<String>, ConversionError> {
+            Ok(t.into_open_tick()?.route_name)
+        }
+
+        let mp_tick = mp_tick();
+
+        assert_eq!(
+            route_name(Box::new(mp_tick))?,
+            Some("a route name".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn merge_fills_blank_fields() -> Result<(), ConversionError> {
+        let mp = OpenTick::try_from(mp_tick())?;
+        let crag = OpenTick {
+            ascent_grade: Some("V3".to_string()),
+            comment: Some("Sandbagged".to_string()),
+            source: Some(Source::TheCrag),
+            ..OpenTick::try_from(mp_tick())?
+        };
+
+        let merged = mp.merge(crag, MergePolicy::PreferFirst);
+
+        assert_eq!(merged.route_grade.as_deref(), Some("V2"));
+        assert_eq!(merged.ascent_grade.as_deref(), Some("V3"));
+        assert_eq!(merged.comment.as_deref(), Some("Sandbagged"));
+        assert_eq!(merged.source, Some(Source::MountainProject));
+
+        Ok(())
+    }
+
+    #[cfg(all(feature = "provenance", feature = "serde"))]
+    #[test]
+    fn merge_provenance() -> Result<(), ConversionError> {
+        let mp = OpenTick::try_from(mp_tick())?;
+        let crag = OpenTick {
+            route_grade: Some("V4".to_string()),
+            ascent_grade: Some("V3".to_string()),
+            comment: Some("".to_string()),
+            source: Some(Source::TheCrag),
+            ..Default::default()
+        };
+
+        let merged = mp.merge(crag, MergePolicy::PreferFirst);
+
+        assert_eq!(merged.provenance["route_grade"], Source::MountainProject);
+        assert_eq!(merged.provenance["ascent_grade"], Source::TheCrag);
+        assert_eq!(merged.provenance.get("comment"), None);
+
+        // Provenance already recorded is kept through further merges
+        let merged = OpenTick::default().merge(merged, MergePolicy::PreferFirst);
+        assert_eq!(merged.provenance["ascent_grade"], Source::TheCrag);
+
+        Ok(())
+    }
+
+    #[test]
+    fn discipline_iter() {
+        let discipline = Discipline {
+            sport: true,
+            trad: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            discipline.iter().collect::<Vec<_>>(),
+            vec![DisciplineKind::Sport, DisciplineKind::Trad]
+        );
+        assert!(discipline.contains(DisciplineKind::Trad));
+        assert!(!discipline.contains(DisciplineKind::Bouldering));
+    }
+
+    #[test]
+    fn discipline_order() {
+        use DisciplineKind::*;
+
+        let canonical = [
+            Aid,
+            Alpine,
+            Bouldering,
+            DeepWaterSolo,
+            Ice,
+            Sport,
+            TopRope,
+            Trad,
+            Unknown,
+        ];
+        let mut reversed = canonical;
+        reversed.reverse();
+
+        assert_eq!(
+            Discipline::from_kinds(&reversed).iter().collect::<Vec<_>>(),
+            canonical
+        );
+        assert!(canonical.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn discipline_from_kinds() {
+        let discipline = Discipline::from_kinds(&[DisciplineKind::Sport, DisciplineKind::Trad]);
+
+        assert_eq!(
+            discipline,
+            Discipline {
+                sport: true,
+                trad: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            Discipline::from_kinds(&discipline.iter().collect::<Vec<_>>()),
+            discipline
+        );
+        assert_eq!(Discipline::from_kinds(&[]), Discipline::default());
+    }
+
+    #[test]
+    fn merge_policies() {
+        let a = OpenTick {
+            comment: Some("Pumpy".to_string()),
+            route_grade: Some("5.10".to_string()),
+            ..Default::default()
+        };
+        let b = OpenTick {
+            comment: Some("Steep and pumpy".to_string()),
+            route_grade: Some("5.10a".to_string()),
+            ..Default::default()
+        };
+        let merge = |policy| a.clone().merge(b.clone(), policy);
+
+        let first = merge(MergePolicy::PreferFirst);
+        assert_eq!(first.comment.as_deref(), Some("Pumpy"));
+        assert_eq!(first.route_grade.as_deref(), Some("5.10"));
+
+        let longer = merge(MergePolicy::PreferLonger);
+        assert_eq!(longer.comment.as_deref(), Some("Steep and pumpy"));
+        assert_eq!(longer.route_grade.as_deref(), Some("5.10a"));
+
+        let both = merge(MergePolicy::Concatenate);
+        assert_eq!(both.comment.as_deref(), Some("Pumpy\nSteep and pumpy"));
+        assert_eq!(both.route_grade.as_deref(), Some("5.10"));
+
+        // Comments already joined aren't repeated
+        let again = both.clone().merge(b.clone(), MergePolicy::Concatenate);
+        assert_eq!(again.comment, both.comment);
+        let same = a.clone().merge(a.clone(), MergePolicy::Concatenate);
+        assert_eq!(same.comment.as_deref(), Some("Pumpy"));
+    }
+
+    #[test]
+    fn outcomes() {
+        let outcome = |style| {
+            OpenTick {
+                ascent_style: Some(style),
+                ..Default::default()
+            }
+            .outcome()
+        };
+
+        assert_eq!(outcome(AscentStyle::Redpoint), Some(Outcome::Send));
+        assert_eq!(outcome(AscentStyle::Attempt), Some(Outcome::Attempt));
+        assert_eq!(outcome(AscentStyle::Hangdog), Some(Outcome::Attempt));
+        assert_eq!(outcome(AscentStyle::Repeat), Some(Outcome::Repeat));
+        assert_eq!(outcome(AscentStyle::TopRope), Some(Outcome::Unknown));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn thecrag_ascent_types() {
+        use TheCragAscentType as T;
+
+        let expected = [
+            (T::Aid, AscentStyle::Aid),
+            (T::AidSolo, AscentStyle::Aid),
+            (T::Attempt, AscentStyle::Attempt),
+            (T::Clean, AscentStyle::Send),
+            (T::Dab, AscentStyle::Dab),
+            (T::Flash, AscentStyle::Flash),
+            (T::Ghost, AscentStyle::Ghost),
+            (T::Greenpoint, AscentStyle::Greenpoint),
+            (T::GreenPointOnsight, AscentStyle::Onsight),
+            (T::GroundUpRedPoint, AscentStyle::Redpoint),
+            (T::HangDog, AscentStyle::Hangdog),
+            (T::LeadSolo, AscentStyle::Solo),
+            (T::Mark, AscentStyle::Unknown),
+            (T::Onsight, AscentStyle::Onsight),
+            (T::PinkPoint, AscentStyle::Pinkpoint),
+            (T::Send, AscentStyle::Send),
+            (T::RedPoint, AscentStyle::Redpoint),
+            (T::Repeat, AscentStyle::Repeat),
+            (T::Retreat, AscentStyle::Attempt),
+            (T::RopedSolo, AscentStyle::Solo),
+            (T::SecondClean, AscentStyle::Second),
+            (T::SecondWithRest, AscentStyle::Second),
+            (T::Tick, AscentStyle::Send),
+            (T::TopRope, AscentStyle::TopRope),
+            (T::TopRopeClean, AscentStyle::TopRope),
+            (T::TopRopeFlash, AscentStyle::TopRope),
+            (T::TopRopeOnsight, AscentStyle::TopRope),
+            (T::TopRopeWithRest, AscentStyle::TopRope),
+            (T::Working, AscentStyle::Hangdog),
+            (T::Other("Bat hang".to_string()), AscentStyle::Unknown),
+        ];
+
+        for (ascent_type, style) in expected {
+            assert_eq!(
+                AscentStyle::from(ascent_type.clone()),
+                style,
+                "{ascent_type:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_discipline() {
+        let lenient: Discipline = "sprot, Trad".parse().unwrap();
+
+        assert_eq!(
+            lenient.iter().collect::<Vec<_>>(),
+            vec![DisciplineKind::Trad, DisciplineKind::Unknown]
+        );
+        assert_eq!(
+            Discipline::try_from_str("sprot"),
+            Err(DisciplineParseError::UnrecognizedToken("sprot".to_string()))
+        );
+        assert_eq!(
+            Discipline::try_from("sport"),
+            Ok(Discipline {
+                sport: true,
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            Discipline::try_from_str("Deep water solo, top_rope"),
+            Ok(Discipline {
+                deep_water_solo: true,
+                top_rope: true,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn sandbagged_grades() {
+        let graded = |route: &str, ascent: &str| OpenTick {
+            route_grade: Some(route.to_string()),
+            ascent_grade: Some(ascent.to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(graded("5.10", "5.11").sandbagged(), Some(Ordering::Greater));
+        assert_eq!(graded("V4", "V3").sandbagged(), Some(Ordering::Less));
+        assert_eq!(graded("6a", "6a").sandbagged(), Some(Ordering::Equal));
+        assert_eq!(graded("5.10", "6a").sandbagged(), None);
+        assert_eq!(graded("5.10", "").sandbagged(), None);
+        assert_eq!(
+            graded("5.10a PG13", "5.10b").sandbagged(),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(OpenTick::default().sandbagged(), None);
+    }
+
+    #[test]
+    fn normalized_grades() {
+        let boulder = OpenTick {
+            route_grade: Some("V5".to_string()),
+            route_discipline: Some(Discipline {
+                bouldering: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let sport = OpenTick {
+            route_grade: Some("5.11d PG13".to_string()),
+            route_discipline: Some(Discipline {
+                sport: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let preferences = GradePreferences::default();
+        let french = preferences
+            .clone()
+            .with(DisciplineKind::Sport, GradeSystem::French);
+
+        assert_eq!(boulder.normalized_grade(&french), Some(Grade::parse("V5")));
+        assert_eq!(sport.normalized_grade(&french), Some(Grade::parse("7a")));
+        assert_eq!(
+            sport.normalized_grade(&preferences),
+            Some(Grade::parse("5.11d"))
+        );
+        assert_eq!(
+            sport.normalized_grade(&french).and_then(|g| g.system()),
+            Some(GradeSystem::French)
+        );
+    }
+
+    #[test]
+    fn grade_parse_statuses() {
+        let graded = |grade: &str| OpenTick {
+            route_grade: Some(grade.to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            graded("6B+").grade_parse_status(),
+            GradeParseStatus::Parsed(GradeSystem::Font)
+        );
+        assert_eq!(
+            graded("HVS").grade_parse_status(),
+            GradeParseStatus::Unrecognized
+        );
+        assert_eq!(graded("").grade_parse_status(), GradeParseStatus::Missing);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn attempts_from_comment() -> Result<(), ConversionError> {
+        let tagged = MountainProjectTick {
+            notes: "Finally sent it #attempts:5".to_string(),
+            ..mp_tick()
+        };
+
+        assert_eq!(OpenTick::try_from(tagged)?.attempts, Some(5));
+        assert_eq!(OpenTick::try_from(mp_tick())?.attempts, None);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn dropped_fields() -> Result<(), ConversionError> {
+        let converted = mp_tick().into_converted_tick()?;
+
+        assert!(converted.dropped.contains(&"rating_code"));
+        assert_eq!(converted.dropped, ["your_stars", "length", "rating_code"]);
+        assert_eq!(converted.tick.route_name.as_deref(), Some("a route name"));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn sparse_tick_less_confident() -> Result<(), ConversionError> {
+        let full = mp_tick().into_converted_tick()?;
+        let sparse = MountainProjectTick {
+            date: None,
+            rating: "?".to_string(),
+            location: String::new(),
+            route_type: "".into(),
+            ..mp_tick()
+        }
+        .into_converted_tick()?;
+
+        assert_eq!(full.confidence, 1.0);
+        assert_eq!(sparse.confidence, 2.0 / 6.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn conversion_error_boxed() {
+        let error: Box<dyn std::error::Error> =
+            Box::new(ConversionError::MissingField("route_name"));
+
+        assert_eq!(
+            error.to_string(),
+            "missing `route_name`, which the target format requires"
+        );
+    }
+
+    #[test]
+    fn thread_safe_types() {
+        // Fails to compile if any of these can't cross threads, as the parallel importer needs
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<OpenTick>();
+        assert_send_sync::<Discipline>();
+        assert_send_sync::<Grade>();
+        assert_send_sync::<logbook::Logbook>();
+        assert_send_sync::<ConversionError>();
+        #[cfg(feature = "serde")]
+        {
+            assert_send_sync::<MountainProjectTick>();
+            assert_send_sync::<TheCragTick>();
+            assert_send_sync::<VerticalLifeTick>();
+            assert_send_sync::<import::ImportError>();
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn route_id_from_url() -> Result<(), ConversionError> {
+        let tick = MountainProjectTick {
+            url: url::Url::parse("https://www.mountainproject.com/route/123456/route-name").ok(),
+            ..mp_tick()
         };
 
-        println!("{t:?}")
+        assert_eq!(
+            OpenTick::try_from(tick)?.route_id(),
+            Some(RouteRef::MountainProject(MountainProjectRouteId(123456)))
+        );
+        assert_eq!(OpenTick::try_from(mp_tick())?.route_id(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn partial_dates() {
+        let parse = |s: &str| s.parse::<PartialDate>();
+
+        assert_eq!(parse("2019"), Ok(PartialDate::Year(2019)));
+        assert_eq!(parse("2019-06"), Ok(PartialDate::YearMonth(2019, 6)));
+        assert_eq!(
+            parse("2019-06-01"),
+            Ok(PartialDate::Full(
+                NaiveDate::from_ymd_opt(2019, 6, 1).expect("valid date")
+            ))
+        );
+        assert!(parse("2019-13").is_err());
+        assert!(parse("June 2019").is_err());
+        assert_eq!(PartialDate::Year(2019).to_string(), "2019");
     }
 }
@@ -0,0 +1,553 @@
+//! A climber's ticks, as a whole
+//!
+//! [`Logbook`] gathers the filters and statistics that work on a collection of ticks behind one
+//! type, so they can be chained.  It dereferences to a slice of ticks, so the functions in
+//! [`stats`](crate::stats) also take a `&Logbook`.
+//!
+//! # Examples
+//! ```
+//! use open_tick::grade::GradePreferences;
+//! use open_tick::logbook::Logbook;
+//! use open_tick::{AscentStyle, DisciplineKind, OpenTick};
+//!
+//! let mut tick = OpenTick::default();
+//! tick.route_name = Some("Sautanz".to_string());
+//! tick.route_grade = Some("7a".to_string());
+//! tick.route_discipline = Some("sport".parse().unwrap());
+//! tick.ascent_style = Some(AscentStyle::Redpoint);
+//!
+//! let logbook = Logbook::from(vec![tick]).filter_discipline(DisciplineKind::Sport);
+//! let hardest = logbook.hardest_send(&GradePreferences::default());
+//! assert_eq!(hardest.and_then(|t| t.route_name.as_deref()), Some("Sautanz"));
+//! ```
+
+use crate::dedup::match_score;
+use crate::grade::{Grade, GradePreferences};
+#[cfg(feature = "serde")]
+use crate::import::{self, ImportError};
+use crate::{DisciplineKind, MergePolicy, OpenTick};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::ops::Deref;
+#[cfg(feature = "serde")]
+use std::path::{Path, PathBuf};
+
+/// A collection of ticks
+#[derive(Debug, Clone, Default)]
+pub struct Logbook(Vec<OpenTick>);
+
+impl Logbook {
+    /// Import exports from several platforms into one logbook
+    ///
+    /// The platform of each file is detected from its header line, as by
+    /// [`import::detect_source`], except that a JSON object is read as a response of Mountain
+    /// Project's API.  The files' ticks are concatenated in order, then records of
+    /// the same ascent merged as by [`Logbook::dedup`] with a threshold of 1.
+    ///
+    /// # Errors
+    /// [`ImportError::UnknownSource`] if a file's platform can't be detected, or any error in
+    /// reading or converting a file, as for [`import::collect_ticks`].
+    #[cfg(feature = "serde")]
+    pub fn from_paths(paths: &[&Path]) -> Result<Logbook, ImportError> {
+        let mut ticks = Vec::new();
+
+        for path in paths {
+            ticks.extend(read_export(path)?.ok_or(ImportError::UnknownSource)?);
+        }
+
+        Ok(Logbook(ticks).dedup(1.0))
+    }
+
+    /// Import every export in a directory into one logbook
+    ///
+    /// As [`Logbook::from_paths`] for the `.csv` and `.json` files directly in `dir`, taken in
+    /// order of their names.  Subdirectories and files with other extensions are ignored.  Files
+    /// that can't be imported, including those whose platform can't be detected, are skipped and
+    /// returned with their errors.
+    ///
+    /// # Errors
+    /// [`ImportError::Io`] if the directory can't be listed.
+    #[cfg(feature = "serde")]
+    pub fn from_dir(dir: &Path) -> Result<(Logbook, Vec<(PathBuf, ImportError)>), ImportError> {
+        let mut paths = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let export = path.extension().is_some_and(|extension| {
+                extension.eq_ignore_ascii_case("csv") || extension.eq_ignore_ascii_case("json")
+            });
+            if export && path.is_file() {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut ticks = Vec::new();
+        let mut skipped = Vec::new();
+        for path in paths {
+            match read_export(&path).and_then(|export| export.ok_or(ImportError::UnknownSource)) {
+                Ok(export) => ticks.extend(export),
+                Err(e) => skipped.push((path, e)),
+            }
+        }
+
+        Ok((Logbook(ticks).dedup(1.0), skipped))
+    }
+
+    /// The ticks, in their current order
+    pub fn into_inner(self) -> Vec<OpenTick> {
+        self.0
+    }
+
+    /// Sort ticks by date, oldest first, with undated ticks last
+    ///
+    /// The sort is stable, so ticks on the same day keep their order.
+    pub fn sort_by_date(mut self) -> Logbook {
+        self.0.sort_by_key(|t| (t.date.is_none(), t.date));
+        self
+    }
+
+    /// Merge ticks that record the same ascent
+    ///
+    /// Ticks whose [`match_score`] is at least `threshold` are merged into the first of them with
+    /// the default [`MergePolicy`].  With a threshold of 1, only ticks that
+    /// [`OpenTick::same_ascent`] finds the same are merged; lower thresholds also merge ticks
+    /// with misspelt names or dates a day apart.  Ticks without a route name are left alone,
+    /// since there is no telling whether two unnamed climbs on the same day were the same one.
+    pub fn dedup(self, threshold: f32) -> Logbook {
+        let mut ticks: Vec<OpenTick> = Vec::with_capacity(self.0.len());
+
+        for tick in self.0 {
+            let duplicate = tick.route_name.is_some().then(|| {
+                ticks
+                    .iter()
+                    .position(|t| t.route_name.is_some() && match_score(t, &tick) >= threshold)
+            });
+
+            match duplicate.flatten() {
+                Some(i) => {
+                    let first = std::mem::take(&mut ticks[i]);
+                    ticks[i] = first.merge(tick, MergePolicy::default());
+                }
+                None => ticks.push(tick),
+            }
+        }
+
+        Logbook(ticks)
+    }
+
+    /// Add the ticks not already in the logbook, returning how many were added
+    ///
+    /// Ticks are told apart by [`OpenTick::identity_hash`], so the same export can be imported
+    /// again, such as to sync with a platform, without adding its ticks twice.  Ticks already in
+    /// the logbook are left as they are, even if an imported copy has been edited since.  Only
+    /// ticks already in the logbook count, so identical ticks in one import, such as two laps of
+    /// a route in a day, are all added.
+    pub fn import_new(&mut self, ticks: impl Iterator<Item = OpenTick>) -> usize {
+        let seen: HashSet<u64> = self.0.iter().map(OpenTick::identity_hash).collect();
+        let before = self.0.len();
+
+        self.0
+            .extend(ticks.filter(|tick| !seen.contains(&tick.identity_hash())));
+
+        self.0.len() - before
+    }
+
+    /// Keep only ticks in the given discipline
+    ///
+    /// Uses the discipline of the ascent if known, otherwise that of the route.  Ticks with no
+    /// known discipline are dropped.
+    pub fn filter_discipline(self, kind: DisciplineKind) -> Logbook {
+        self.0
+            .into_iter()
+            .filter(|t| {
+                t.ascent_discipline
+                    .as_ref()
+                    .or(t.route_discipline.as_ref())
+                    .is_some_and(|d| d.contains(kind))
+            })
+            .collect()
+    }
+
+    /// Split ticks into those climbed outdoors and those in a gym, in that order
+    ///
+    /// Ticks not known to be indoors count as outdoors.
+    pub fn partition_indoor(&self) -> (Vec<&OpenTick>, Vec<&OpenTick>) {
+        let (indoor, outdoor) = self.0.iter().partition(|t| t.indoor == Some(true));
+        (outdoor, indoor)
+    }
+
+    /// Replace partners' names with placeholders, such as before sharing the logbook
+    ///
+    /// Each partner becomes `Partner 1`, `Partner 2` and so on, in order of first appearance, so
+    /// the same partner keeps the same placeholder throughout.  Their names are also replaced
+    /// where they appear as whole words in comments, and any other `@mention` in a comment
+    /// becomes `@partner`.  With the `raw` feature, the source records are dropped too, since
+    /// they hold the names as exported.
+    pub fn anonymize_partners(&mut self) {
+        let mut placeholders: Vec<(String, String)> = Vec::new();
+
+        for partners in self.0.iter_mut().filter_map(|t| t.partners.as_mut()) {
+            for partner in partners {
+                let placeholder = match placeholders.iter().find(|(name, _)| name == partner) {
+                    Some((_, placeholder)) => placeholder.clone(),
+                    None => {
+                        let placeholder = format!("Partner {}", placeholders.len() + 1);
+                        placeholders.push((partner.clone(), placeholder.clone()));
+                        placeholder
+                    }
+                };
+                *partner = placeholder;
+            }
+        }
+
+        // Longest first, so that a name containing another is replaced whole
+        placeholders.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+
+        for comment in self.0.iter_mut().filter_map(|t| t.comment.as_mut()) {
+            for (name, placeholder) in &placeholders {
+                *comment = replace_word(comment, name, placeholder);
+            }
+            *comment = scrub_mentions(comment);
+        }
+
+        #[cfg(feature = "raw")]
+        for tick in &mut self.0 {
+            tick.raw = None;
+        }
+    }
+
+    /// The send with the hardest grade
+    ///
+    /// Grades are normalized as by [`OpenTick::normalized_grade`] and compared as [`Grade`]s.
+    /// Sends whose grades don't compare with the hardest so far, such as a boulder against a
+    /// route, are skipped, so filter by discipline first to compare like with like.  Of equally
+    /// hard sends, the first is returned.
+    pub fn hardest_send(&self, preferences: &GradePreferences) -> Option<&OpenTick> {
+        let mut hardest: Option<(&OpenTick, Grade)> = None;
+
+        for tick in self
+            .0
+            .iter()
+            .filter(|t| t.ascent_style.is_some_and(|s| s.is_send()))
+        {
+            let Some(grade) = tick.normalized_grade(preferences) else {
+                continue;
+            };
+
+            let harder = hardest
+                .as_ref()
+                .is_none_or(|(_, best)| grade.partial_cmp(best) == Some(Ordering::Greater));
+            if harder {
+                hardest = Some((tick, grade));
+            }
+        }
+
+        hardest.map(|(tick, _)| tick)
+    }
+
+    /// Combine with another logbook, such as one exported from a different platform
+    ///
+    /// The ticks of `other` are appended, then records of the same ascent are merged as by
+    /// [`Logbook::dedup`] with a threshold of 1, preferring the values in `self`.
+    pub fn merge(mut self, other: Logbook) -> Logbook {
+        self.0.extend(other.0);
+        self.dedup(1.0)
+    }
+}
+
+/// `text` with each occurrence of `word` that isn't part of a longer word replaced by `with`
+fn replace_word(text: &str, word: &str, with: &str) -> String {
+    if word.is_empty() {
+        return text.to_string();
+    }
+
+    let mut replaced = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(at) = rest.find(word) {
+        let (before, after) = (&rest[..at], &rest[at + word.len()..]);
+        let bounded =
+            !before.ends_with(char::is_alphanumeric) && !after.starts_with(char::is_alphanumeric);
+
+        replaced.push_str(before);
+        replaced.push_str(if bounded { with } else { word });
+        rest = after;
+    }
+    replaced.push_str(rest);
+
+    replaced
+}
+
+/// `text` with each `@mention` replaced by `@partner`
+///
+/// A mention is an `@` at the start of a word, so email addresses are left alone.
+fn scrub_mentions(text: &str) -> String {
+    let mut scrubbed = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut word_start = true;
+
+    while let Some(c) = chars.next() {
+        if c == '@' && word_start && chars.peek().is_some_and(|&c| is_handle_char(c)) {
+            while chars.next_if(|&c| is_handle_char(c)).is_some() {}
+            scrubbed.push_str("@partner");
+            word_start = false;
+            continue;
+        }
+
+        scrubbed.push(c);
+        word_start = c.is_whitespace() || c == '(';
+    }
+
+    scrubbed
+}
+
+fn is_handle_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.')
+}
+
+impl Deref for Logbook {
+    type Target = [OpenTick];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<OpenTick>> for Logbook {
+    fn from(ticks: Vec<OpenTick>) -> Self {
+        Logbook(ticks)
+    }
+}
+
+impl FromIterator<OpenTick> for Logbook {
+    fn from_iter<I: IntoIterator<Item = OpenTick>>(iter: I) -> Self {
+        Logbook(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for Logbook {
+    type Item = OpenTick;
+    type IntoIter = std::vec::IntoIter<OpenTick>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// The ticks of the export at `path`, `None` if its platform can't be detected
+///
+/// A JSON object is read as a response of Mountain Project's API, and anything else as a CSV
+/// export.
+#[cfg(feature = "serde")]
+fn read_export(path: &Path) -> Result<Option<Vec<OpenTick>>, ImportError> {
+    let export = std::fs::read(path)?;
+    if export.trim_ascii_start().starts_with(b"{") {
+        return import::read_mountain_project_api(export.as_slice()).map(Some);
+    }
+
+    let header = String::from_utf8_lossy(export.split(|&b| b == b'\n').next().unwrap_or_default());
+
+    match import::detect_source(&header) {
+        Some(source) => import::collect_ticks(export.as_slice(), source).map(Some),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AscentStyle;
+    use chrono::NaiveDate;
+    use pretty_assertions::assert_eq;
+
+    fn tick(day: u32, name: &str, grade: &str, style: AscentStyle) -> OpenTick {
+        OpenTick {
+            date: NaiveDate::from_ymd_opt(2023, 1, day),
+            route_name: Some(name.to_string()),
+            route_grade: Some(grade.to_string()),
+            route_discipline: Some("sport".parse().unwrap()),
+            ascent_style: Some(style),
+            ..Default::default()
+        }
+    }
+
+    fn names(logbook: &Logbook) -> Vec<&str> {
+        logbook
+            .iter()
+            .map(|t| t.route_name.as_deref().unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn sort_and_dedup() {
+        let logbook = Logbook::from(vec![
+            tick(3, "C", "5.10a", AscentStyle::Redpoint),
+            OpenTick::default(),
+            tick(1, "A", "5.10a", AscentStyle::Redpoint),
+            OpenTick {
+                comment: Some("Windy".to_string()),
+                ..tick(1, "a", "5.10a", AscentStyle::Redpoint)
+            },
+            OpenTick::default(),
+        ]);
+
+        let logbook = logbook.sort_by_date().dedup(1.0);
+
+        assert_eq!(names(&logbook), ["A", "C", "", ""]);
+        assert_eq!(logbook[0].comment.as_deref(), Some("Windy"));
+    }
+
+    #[test]
+    fn dedup_threshold() {
+        let logbook = Logbook::from(vec![
+            tick(1, "Classic Crack", "5.10a", AscentStyle::Redpoint),
+            tick(1, "Clasic Crack", "5.10a", AscentStyle::Redpoint),
+        ]);
+
+        assert_eq!(
+            names(&logbook.clone().dedup(1.0)),
+            ["Classic Crack", "Clasic Crack"]
+        );
+        assert_eq!(names(&logbook.dedup(0.8)), ["Classic Crack"]);
+    }
+
+    #[test]
+    fn merge_platforms() {
+        let ours = Logbook::from(vec![tick(1, "A", "5.10a", AscentStyle::Redpoint)]);
+        let theirs = Logbook::from(vec![
+            OpenTick {
+                comment: Some("Finally".to_string()),
+                ..tick(1, "A", "6a", AscentStyle::Redpoint)
+            },
+            tick(2, "B", "6a", AscentStyle::Flash),
+        ]);
+
+        let merged = ours.merge(theirs);
+
+        assert_eq!(names(&merged), ["A", "B"]);
+        assert_eq!(merged[0].route_grade.as_deref(), Some("5.10a"));
+        assert_eq!(merged[0].comment.as_deref(), Some("Finally"));
+    }
+
+    #[test]
+    fn hardest_send_skips_attempts() {
+        let logbook = Logbook::from(vec![
+            tick(1, "Warm-up", "5.9", AscentStyle::Onsight),
+            tick(2, "Project", "5.12a", AscentStyle::Attempt),
+            tick(3, "Send", "6c", AscentStyle::Redpoint),
+            tick(4, "Also 6c", "6c", AscentStyle::Redpoint),
+        ]);
+
+        let hardest = logbook.hardest_send(&GradePreferences::default());
+
+        assert_eq!(hardest.and_then(|t| t.route_name.as_deref()), Some("Send"));
+        assert!(Logbook::default()
+            .hardest_send(&GradePreferences::default())
+            .is_none());
+    }
+
+    #[test]
+    fn partition_gym_sessions() {
+        let logbook = Logbook::from(vec![
+            OpenTick {
+                indoor: Some(true),
+                ..tick(1, "Gym", "5.10a", AscentStyle::Flash)
+            },
+            OpenTick {
+                indoor: Some(false),
+                ..tick(2, "Crag", "5.10a", AscentStyle::Flash)
+            },
+            tick(3, "Unknown", "5.10a", AscentStyle::Flash),
+        ]);
+
+        let (outdoor, indoor) = logbook.partition_indoor();
+        let outdoor: Logbook = outdoor.into_iter().cloned().collect();
+        let indoor: Logbook = indoor.into_iter().cloned().collect();
+
+        assert_eq!(names(&outdoor), ["Crag", "Unknown"]);
+        assert_eq!(names(&indoor), ["Gym"]);
+    }
+
+    #[test]
+    fn anonymized_partners() {
+        let mut logbook = Logbook::from(vec![
+            OpenTick {
+                partners: Some(vec!["Jo Bloggs".to_string(), "Sam".to_string()]),
+                comment: Some("Sam led, Jo Bloggs cleaned.  Thanks @sam_climbs!".to_string()),
+                ..tick(1, "A", "5.10a", AscentStyle::Redpoint)
+            },
+            OpenTick {
+                partners: Some(vec!["Sam".to_string()]),
+                comment: Some("Mail photos to me@example.com".to_string()),
+                ..tick(2, "B", "5.10a", AscentStyle::Redpoint)
+            },
+        ]);
+
+        logbook.anonymize_partners();
+
+        assert_eq!(
+            logbook[0].partners,
+            Some(vec!["Partner 1".to_string(), "Partner 2".to_string()])
+        );
+        assert_eq!(logbook[1].partners, Some(vec!["Partner 2".to_string()]));
+        assert_eq!(
+            logbook[0].comment.as_deref(),
+            Some("Partner 2 led, Partner 1 cleaned.  Thanks @partner!")
+        );
+        assert_eq!(
+            logbook[1].comment.as_deref(),
+            Some("Mail photos to me@example.com")
+        );
+
+        let everything = format!("{logbook:?}");
+        for name in ["Jo", "Bloggs", "Sam", "sam_climbs"] {
+            assert!(!everything.contains(name), "{name} remains");
+        }
+    }
+
+    #[test]
+    fn import_same_day_laps() {
+        let lap = || tick(1, "A", "5.10a", AscentStyle::Redpoint);
+        let mut logbook = Logbook::from(vec![tick(2, "B", "5.10a", AscentStyle::Redpoint)]);
+
+        assert_eq!(logbook.import_new([lap(), lap()].into_iter()), 2);
+        assert_eq!(logbook.import_new([lap()].into_iter()), 0);
+        assert_eq!(logbook.len(), 3);
+    }
+
+    #[test]
+    fn anonymized_whole_words() {
+        let mut logbook = Logbook::from(vec![OpenTick {
+            partners: Some(vec!["Al".to_string()]),
+            comment: Some("Alpine start with Al, then Al's lead".to_string()),
+            ..tick(1, "A", "5.10a", AscentStyle::Redpoint)
+        }]);
+
+        logbook.anonymize_partners();
+
+        assert_eq!(
+            logbook[0].comment.as_deref(),
+            Some("Alpine start with Partner 1, then Partner 1's lead")
+        );
+    }
+
+    #[cfg(feature = "raw")]
+    #[test]
+    fn anonymized_raw_tick() -> Result<(), crate::ConversionError> {
+        let exported = crate::TheCragTick::try_from(OpenTick {
+            partners: Some(vec!["Jo Bloggs".to_string()]),
+            comment: Some("Jo Bloggs belayed".to_string()),
+            ..tick(1, "A", "24", AscentStyle::Redpoint)
+        })?;
+        let mut logbook = Logbook::from(vec![OpenTick::try_from(exported)?]);
+        assert!(logbook[0].raw.is_some());
+
+        logbook.anonymize_partners();
+
+        let everything = format!("{logbook:?}");
+        for name in ["Jo", "Bloggs"] {
+            assert!(!everything.contains(name), "{name} remains");
+        }
+
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use crate::de::{option_or_other, or_other, OrOther, Unrecognized};
 use chrono::NaiveDate;
 use serde::de::{self, Deserialize, Deserializer, Visitor};
 use serde::{Serialize, Serializer};
@@ -7,43 +8,60 @@ use url::Url;
 
 /// A tick as recorded in an export from
 /// `https://www.mountainproject.com/user/<userid>/<username>/tick-export`
-#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+///
+/// Columns may also be named in snake case, e.g. `avg_stars` for `Avg Stars`, as in CSVs
+/// generated by scripts.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct MountainProjectTick {
-    #[serde(rename = "Date")]
+    /// Accepts both ISO `YYYY-MM-DD` and US `MM/DD/YYYY` dates
+    #[serde(rename = "Date", alias = "date", deserialize_with = "deserialize_date")]
     pub date: Option<NaiveDate>,
 
     /// Mountain Project assigned name
-    #[serde(rename = "Route")]
+    #[serde(rename = "Route", alias = "route")]
     pub route: String,
 
     /// Mountain Project assigned grade
-    #[serde(rename = "Rating")]
+    #[serde(rename = "Rating", alias = "rating")]
     pub rating: String,
 
-    #[serde(rename = "Notes")]
+    #[serde(rename = "Notes", alias = "notes")]
     pub notes: String,
 
     /// URL of route on <https://www.mountainproject.com>
-    #[serde(rename = "URL")]
+    #[serde(rename = "URL", alias = "url")]
     pub url: Option<Url>,
 
-    #[serde(rename = "Pitches")]
-    pub pitches: u8,
+    /// Number of pitches, `None` if blank
+    #[serde(rename = "Pitches", alias = "pitches")]
+    pub pitches: Option<u16>,
 
-    #[serde(rename = "Location")]
+    #[serde(rename = "Location", alias = "location")]
     pub location: String,
 
-    #[serde(rename = "Avg Stars")]
-    pub avg_stars: f32,
+    /// Average of all users' star ratings, `None` if the route is unrated
+    ///
+    /// The export marks unrated routes with `-1`, which reads as `None`.  Accepts both `.` and
+    /// `,` as the decimal separator.
+    #[serde(
+        rename = "Avg Stars",
+        alias = "avg_stars",
+        deserialize_with = "deserialize_avg_stars"
+    )]
+    pub avg_stars: Option<f32>,
 
     /// -1 if no rating, 1-5 otherwise
-    #[serde(rename = "Your Stars")]
+    #[serde(rename = "Your Stars", alias = "your_stars")]
     pub your_stars: i8,
 
-    #[serde(rename = "Style")]
+    #[serde(rename = "Style", alias = "style", deserialize_with = "or_other")]
     pub style: MountainProjectStyle,
 
-    #[serde(rename = "Lead Style")]
+    #[serde(
+        rename = "Lead Style",
+        alias = "lead_style",
+        deserialize_with = "option_or_other"
+    )]
     pub lead_style: Option<MountainProjectLeadStyle>,
 
     /// The type of route climbed
@@ -53,25 +71,189 @@ pub struct MountainProjectTick {
     /// Sport
     /// "Sport, TR"
     /// ```
-    #[serde(rename = "Route Type")]
+    #[serde(rename = "Route Type", alias = "route_type")]
     pub route_type: MountainProjectRouteType,
 
     /// ticker's own grade, which may differ from "official" grade
-    #[serde(rename = "Your Rating")]
+    #[serde(rename = "Your Rating", alias = "your_rating")]
     pub your_rating: String,
 
     /// length of route in feet
-    #[serde(rename = "Length")]
+    #[serde(rename = "Length", alias = "length")]
     pub length: usize,
 
     /// unclear meaning, u16 might suffice
-    #[serde(rename = "Rating Code")]
+    #[serde(rename = "Rating Code", alias = "rating_code")]
     pub rating_code: u32,
 }
 
+impl MountainProjectTick {
+    /// Populated fields that have no counterpart in [`OpenTick`](crate::OpenTick)
+    pub(crate) fn dropped_fields(&self) -> Vec<&'static str> {
+        [
+            ("your_stars", self.your_stars != -1),
+            ("length", self.length != 0),
+            ("rating_code", self.rating_code != 0),
+        ]
+        .into_iter()
+        .filter_map(|(field, populated)| populated.then_some(field))
+        .collect()
+    }
+
+    /// Whether the tick should have a lead style
+    ///
+    /// Mountain Project only asks for a lead style on roped ascents, led or followed, so a blank
+    /// lead style on a top rope, solo or boulder tick is expected rather than missing data.
+    pub fn is_lead_style_expected(&self) -> bool {
+        matches!(
+            self.style,
+            MountainProjectStyle::Lead | MountainProjectStyle::Follow
+        )
+    }
+
+    /// Whether the lead style is blank where the style calls for one
+    pub fn is_lead_style_missing(&self) -> bool {
+        self.is_lead_style_expected() && self.lead_style.is_none()
+    }
+
+    /// The style and lead style together, such as `Lead — Redpoint` or `TR`
+    ///
+    /// Styles are named as in Mountain Project's export.  Blank styles are left out.  Conversion
+    /// warnings name unrecognized styles this way.
+    pub fn describe_ascent(&self) -> String {
+        let style = self.style.to_string();
+        let lead_style = self.lead_style.as_ref().map(|s| s.to_string());
+
+        [Some(style), lead_style]
+            .into_iter()
+            .flatten()
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(" — ")
+    }
+}
+
+/// A tick as returned by Mountain Project's JSON API, with the route nested in it
+///
+/// Fields are named in camel case, as in the API's responses.  Convert to a
+/// [`MountainProjectTick`] to use the same conversion as for CSV exports.
+///
+/// # Examples
+/// ```
+/// use open_tick::mountain_project::MountainProjectApiTick;
+/// use open_tick::MountainProjectTick;
+///
+/// let json = r#"{
+///     "date": "2023-06-01",
+///     "style": "Lead",
+///     "leadStyle": "Onsight",
+///     "route": {"id": 105748391, "name": "The Nose", "type": "Trad", "rating": "5.9"}
+/// }"#;
+///
+/// let tick: MountainProjectApiTick = serde_json::from_str(json).expect("valid tick");
+/// let tick = MountainProjectTick::from(tick);
+/// assert_eq!(tick.route, "The Nose");
+/// ```
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MountainProjectApiTick {
+    pub tick_id: Option<u64>,
+
+    #[serde(default, deserialize_with = "deserialize_date")]
+    pub date: Option<NaiveDate>,
+
+    #[serde(default)]
+    pub notes: String,
+
+    /// Number of pitches climbed, `None` if not given
+    pub pitches: Option<u16>,
+
+    #[serde(deserialize_with = "or_other")]
+    pub style: MountainProjectStyle,
+
+    #[serde(default, deserialize_with = "option_or_other")]
+    pub lead_style: Option<MountainProjectLeadStyle>,
+
+    /// The ticker's own star rating, `None` or -1 if they gave none
+    pub user_stars: Option<i8>,
+
+    /// The ticker's own grade, which may differ from the route's
+    #[serde(default)]
+    pub user_rating: String,
+
+    pub route: MountainProjectApiRoute,
+}
+
+/// A route as nested in a [`MountainProjectApiTick`]
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MountainProjectApiRoute {
+    pub id: usize,
+
+    pub name: String,
+
+    #[serde(rename = "type")]
+    pub route_type: MountainProjectRouteType,
+
+    /// Mountain Project assigned grade
+    pub rating: String,
+
+    /// Average of all users' star ratings, `None` if the route is unrated
+    pub stars: Option<f32>,
+
+    pub pitches: Option<u16>,
+
+    /// Areas containing the route, from broadest to most specific
+    #[serde(default)]
+    pub location: Vec<String>,
+
+    /// URL of the route, built from its ID if not given
+    #[serde(default, deserialize_with = "crate::de::lenient_url")]
+    pub url: Option<Url>,
+}
+
+impl From<MountainProjectApiTick> for MountainProjectTick {
+    fn from(value: MountainProjectApiTick) -> Self {
+        let route = value.route;
+        let url = route.url.or_else(|| {
+            Url::parse(&format!(
+                "https://www.mountainproject.com/route/{}",
+                route.id
+            ))
+            .ok()
+        });
+
+        MountainProjectTick {
+            date: value.date,
+            route: route.name,
+            rating: route.rating,
+            notes: value.notes,
+            url,
+            pitches: value.pitches.or(route.pitches),
+            location: route.location.join(" > "),
+            avg_stars: route.stars.filter(|&s| s >= 0.0),
+            your_stars: value.user_stars.unwrap_or(-1),
+            style: value.style,
+            lead_style: value.lead_style,
+            route_type: route.route_type,
+            your_rating: value.user_rating,
+            length: 0,
+            rating_code: 0,
+        }
+    }
+}
+
+impl Unrecognized for MountainProjectTick {
+    fn unrecognized_value(&self) -> Option<&str> {
+        self.style
+            .as_other()
+            .or_else(|| self.lead_style.as_ref()?.as_other())
+    }
+}
+
 /// Styles of ascent allowed by Mountain Project
 #[non_exhaustive]
-#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum MountainProjectStyle {
     /// only for boulders
     Attempt,
@@ -87,17 +269,79 @@ pub enum MountainProjectStyle {
     Solo,
     /// not for boulders
     TR,
+    /// A style not known to this crate
+    #[serde(untagged, skip_deserializing)]
+    Other(String),
+}
+
+impl OrOther for MountainProjectStyle {
+    fn other(value: String) -> Self {
+        MountainProjectStyle::Other(value)
+    }
+
+    fn as_other(&self) -> Option<&str> {
+        match self {
+            MountainProjectStyle::Other(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Name as in Mountain Project's export, e.g. `TR`
+impl fmt::Display for MountainProjectStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            MountainProjectStyle::Attempt => "Attempt",
+            MountainProjectStyle::Flash => "Flash",
+            MountainProjectStyle::Follow => "Follow",
+            MountainProjectStyle::Lead => "Lead",
+            MountainProjectStyle::Send => "Send",
+            MountainProjectStyle::Solo => "Solo",
+            MountainProjectStyle::TR => "TR",
+            MountainProjectStyle::Other(value) => value,
+        })
+    }
 }
 
 /// Sub-styles for lead ascents
 #[non_exhaustive]
-#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum MountainProjectLeadStyle {
     FellHung,
     Flash,
     Onsight,
     Pinkpoint,
     Redpoint,
+    /// A lead style not known to this crate
+    #[serde(untagged, skip_deserializing)]
+    Other(String),
+}
+
+impl OrOther for MountainProjectLeadStyle {
+    fn other(value: String) -> Self {
+        MountainProjectLeadStyle::Other(value)
+    }
+
+    fn as_other(&self) -> Option<&str> {
+        match self {
+            MountainProjectLeadStyle::Other(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Name as in Mountain Project's export, e.g. `Fell/Hung`
+impl fmt::Display for MountainProjectLeadStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            MountainProjectLeadStyle::FellHung => "Fell/Hung",
+            MountainProjectLeadStyle::Flash => "Flash",
+            MountainProjectLeadStyle::Onsight => "Onsight",
+            MountainProjectLeadStyle::Pinkpoint => "Pinkpoint",
+            MountainProjectLeadStyle::Redpoint => "Redpoint",
+            MountainProjectLeadStyle::Other(value) => value,
+        })
+    }
 }
 
 /// Discipline of a route from Mountain Project
@@ -120,9 +364,14 @@ pub enum MountainProjectLeadStyle {
 /// assert!(deserialized.trad == true);
 /// ```
 #[non_exhaustive]
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct MountainProjectRouteType {
+    pub aid: bool,
+    pub alpine: bool,
     pub boulder: bool,
+    pub ice: bool,
+    /// Mixed rock and ice
+    pub mixed: bool,
     pub sport: bool,
     pub top_rope: bool,
     pub trad: bool,
@@ -133,7 +382,11 @@ pub struct MountainProjectRouteType {
 impl From<&str> for MountainProjectRouteType {
     fn from(value: &str) -> Self {
         MountainProjectRouteType {
+            aid: value.contains("Aid"),
+            alpine: value.contains("Alpine"),
             boulder: value.contains("Boulder"),
+            ice: value.contains("Ice"),
+            mixed: value.contains("Mixed"),
             sport: value.contains("Sport"),
             top_rope: value.contains("TR"),
             trad: value.contains("Trad"),
@@ -187,6 +440,18 @@ impl Serialize for MountainProjectRouteType {
         if self.trad {
             s.push("trad");
         }
+        if self.aid {
+            s.push("Aid");
+        }
+        if self.ice {
+            s.push("Ice");
+        }
+        if self.mixed {
+            s.push("Mixed");
+        }
+        if self.alpine {
+            s.push("Alpine");
+        }
         if self.unknown {
             s.push("Unknown");
         }
@@ -197,6 +462,134 @@ impl Serialize for MountainProjectRouteType {
     }
 }
 
+/// Date formats used in Mountain Project exports, other than ISO `YYYY-MM-DD`
+const DATE_FORMATS: [&str; 1] = ["%m/%d/%Y"];
+
+struct DateVisitor;
+
+impl<'de> Visitor<'de> for DateVisitor {
+    type Value = Option<NaiveDate>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a date as YYYY-MM-DD or MM/DD/YYYY, or an empty string")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(None);
+        }
+
+        // ISO dates are by far the most common, and parse much faster without a format string
+        value
+            .parse::<NaiveDate>()
+            .ok()
+            .or_else(|| {
+                DATE_FORMATS
+                    .iter()
+                    .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+            })
+            .map(Some)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(None)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+fn deserialize_date<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_option(DateVisitor)
+}
+
+struct AvgStarsVisitor;
+
+impl<'de> Visitor<'de> for AvgStarsVisitor {
+    type Value = Option<f32>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a decimal number, or an empty string")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(None);
+        }
+
+        // Some locales use a comma as the decimal separator
+        value
+            .replace(',', ".")
+            .parse::<f32>()
+            .map(Some)
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Some(value as f32))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Some(value as f32))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Some(value as f32))
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(None)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(None)
+    }
+}
+
+fn deserialize_avg_stars<'de, D>(deserializer: D) -> Result<Option<f32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    // Unrated routes are exported as -1
+    deserializer
+        .deserialize_any(AvgStarsVisitor)
+        .map(|stars| stars.filter(|&s| s >= 0.0))
+}
+
 /// ID of a route in Mountain Project's database
 ///
 /// # Examples
@@ -211,7 +604,7 @@ impl Serialize for MountainProjectRouteType {
 /// let mp_id = MountainProjectRouteId::try_from(url).expect("valid route url");
 /// assert_eq!(mp_id, MountainProjectRouteId(12321))
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct MountainProjectRouteId(pub usize);
 
 impl TryFrom<Url> for MountainProjectRouteId {
@@ -247,20 +640,56 @@ impl TryFrom<Url> for MountainProjectRouteId {
     }
 }
 
+/// Errors in reading a [`MountainProjectRouteId`] from a URL
 #[non_exhaustive]
 #[derive(Debug, PartialEq)]
 pub enum MountainProjectIdConversionError {
+    /// The URL isn't on `www.mountainproject.com`
     WrongDomain,
+    /// The URL's path isn't that of a route, such as `/route/<id>/<name>`
     BadPath,
 }
 
+impl fmt::Display for MountainProjectIdConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            MountainProjectIdConversionError::WrongDomain => "URL is not on mountainproject.com",
+            MountainProjectIdConversionError::BadPath => "URL path is not a route",
+        })
+    }
+}
+
+impl std::error::Error for MountainProjectIdConversionError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
     use std::convert::TryFrom;
 
+    /// A lead of a single-pitch sport route, with nothing else recorded
+    fn mp_tick() -> MountainProjectTick {
+        MountainProjectTick {
+            date: None,
+            route: "a route name".to_string(),
+            rating: "5.10a".to_string(),
+            notes: String::new(),
+            url: None,
+            pitches: Some(1),
+            location: "A place > the crag".to_string(),
+            avg_stars: None,
+            your_stars: -1,
+            style: MountainProjectStyle::Lead,
+            lead_style: None,
+            route_type: "Sport".into(),
+            your_rating: String::new(),
+            length: 0,
+            rating_code: 0,
+        }
+    }
+
     #[test]
+    #[allow(clippy::ok_expect)]
     fn mp_route_url_good() -> Result<(), MountainProjectIdConversionError> {
         let id = 123456;
         let url = Url::parse(&format!(
@@ -275,6 +704,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::ok_expect)]
     fn mp_route_url_is_area() {
         let id = 123456;
         let url = Url::parse(&format!(
@@ -289,6 +719,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::ok_expect)]
     fn mp_route_url_wrong_domain() {
         let id = 123456;
         let url = Url::parse(&format!(
@@ -303,6 +734,19 @@ mod tests {
     }
 
     #[test]
+    fn id_error_messages() {
+        assert_eq!(
+            MountainProjectIdConversionError::WrongDomain.to_string(),
+            "URL is not on mountainproject.com"
+        );
+        assert_eq!(
+            MountainProjectIdConversionError::BadPath.to_string(),
+            "URL path is not a route"
+        );
+    }
+
+    #[test]
+    #[allow(clippy::unnecessary_fallible_conversions)]
     fn init_tick() {
         let t: MountainProjectTick = MountainProjectTick {
             date: NaiveDate::from_ymd_opt(2020, 1, 1),
@@ -310,9 +754,9 @@ mod tests {
             rating: "V2".to_string(),
             notes: "fund route".to_string(),
             url: Url::parse("https://www.mountainproject.com/route/123456/route-name").ok(),
-            pitches: 1,
+            pitches: Some(1),
             location: "A place > the crag".to_string(),
-            avg_stars: 3.2,
+            avg_stars: Some(3.2),
             your_stars: 3,
             style: MountainProjectStyle::TR,
             lead_style: Some(MountainProjectLeadStyle::FellHung),
@@ -326,6 +770,171 @@ mod tests {
     }
 
     #[test]
+    fn ascent_description() {
+        let tick = |style, lead_style| MountainProjectTick {
+            style,
+            lead_style,
+            ..mp_tick()
+        };
+
+        assert_eq!(
+            tick(
+                MountainProjectStyle::Lead,
+                Some(MountainProjectLeadStyle::Redpoint)
+            )
+            .describe_ascent(),
+            "Lead — Redpoint"
+        );
+        assert_eq!(tick(MountainProjectStyle::TR, None).describe_ascent(), "TR");
+        assert_eq!(
+            tick(
+                MountainProjectStyle::Lead,
+                Some(MountainProjectLeadStyle::Other(String::new()))
+            )
+            .describe_ascent(),
+            "Lead"
+        );
+    }
+
+    #[test]
+    fn unrecognized_ascent_warning() -> Result<(), crate::ConversionError> {
+        use crate::IntoOpenTick;
+
+        let converted = MountainProjectTick {
+            lead_style: Some(MountainProjectLeadStyle::Other("Bat hang".to_string())),
+            ..mp_tick()
+        }
+        .into_converted_tick()?;
+
+        assert_eq!(
+            converted.warnings,
+            [r#"unrecognized ascent style "Lead — Bat hang""#]
+        );
+        assert!(mp_tick().into_converted_tick()?.warnings.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn lead_style_expected() {
+        let tick = |style| MountainProjectTick {
+            style,
+            route_type: "Sport, TR".into(),
+            ..mp_tick()
+        };
+
+        let top_rope = tick(MountainProjectStyle::TR);
+        assert!(!top_rope.is_lead_style_expected());
+        assert!(!top_rope.is_lead_style_missing());
+
+        let lead = tick(MountainProjectStyle::Lead);
+        assert!(lead.is_lead_style_missing());
+        assert!(!MountainProjectTick {
+            lead_style: Some(MountainProjectLeadStyle::Onsight),
+            ..lead
+        }
+        .is_lead_style_missing());
+    }
+
+    #[test]
+    fn mountain_route_types() {
+        let route_type = MountainProjectRouteType::from("Trad, Ice");
+        assert_eq!(
+            route_type,
+            MountainProjectRouteType {
+                ice: true,
+                trad: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            crate::Discipline::from(route_type),
+            "trad, ice".parse().unwrap()
+        );
+
+        let route_type = MountainProjectRouteType::from("Trad, Aid, Mixed, Alpine");
+        assert!(route_type.aid && route_type.mixed && route_type.alpine);
+        assert!(!route_type.ice && !route_type.sport);
+    }
+
+    #[test]
+    fn date_formats() -> Result<(), Box<dyn std::error::Error>> {
+        let mp_csv = r#"Date,Route,Rating,Notes,URL,Pitches,Location,"Avg Stars","Your Stars",Style,"Lead Style","Route Type","Your Rating",Length,"Rating Code"
+2023-06-01,"Route Name",V1,,https://www.mountainproject.com/route/271828/route-name,1,"Area > Crag",3.2,-1,Send,,Boulder,,10,20300
+06/01/2023,"Route Name",V1,,https://www.mountainproject.com/route/271828/route-name,1,"Area > Crag",3.2,-1,Send,,Boulder,,10,20300
+,"Route Name",V1,,https://www.mountainproject.com/route/271828/route-name,1,"Area > Crag",3.2,-1,Send,,Boulder,,10,20300
+"#;
+
+        let mut reader = csv::Reader::from_reader(mp_csv.as_bytes());
+        let dates = reader
+            .deserialize()
+            .map(|record| record.map(|r: MountainProjectTick| r.date))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let expected = NaiveDate::from_ymd_opt(2023, 6, 1);
+        assert_eq!(dates, vec![expected, expected, None]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn pitches() -> Result<(), Box<dyn std::error::Error>> {
+        let mp_csv = r#"Date,Route,Rating,Notes,URL,Pitches,Location,"Avg Stars","Your Stars",Style,"Lead Style","Route Type","Your Rating",Length,"Rating Code"
+2023-06-01,"Route Name",V1,,https://www.mountainproject.com/route/271828/route-name,,"Area > Crag",3.2,-1,Send,,Boulder,,10,20300
+2023-06-01,"Route Name",5.9,,https://www.mountainproject.com/route/271828/route-name,300,"Area > Crag",3.2,-1,Lead,,Trad,,3000,20300
+"#;
+
+        let mut reader = csv::Reader::from_reader(mp_csv.as_bytes());
+        let pitches = reader
+            .deserialize()
+            .map(|record| record.map(|r: MountainProjectTick| r.pitches))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        assert_eq!(pitches, vec![None, Some(300)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn avg_stars_decimal_separators() -> Result<(), Box<dyn std::error::Error>> {
+        let mp_csv = r#"Date,Route,Rating,Notes,URL,Pitches,Location,"Avg Stars","Your Stars",Style,"Lead Style","Route Type","Your Rating",Length,"Rating Code"
+2023-06-01,"Route Name",V1,,https://www.mountainproject.com/route/271828/route-name,1,"Area > Crag",3.2,-1,Send,,Boulder,,10,20300
+2023-06-01,"Route Name",V1,,https://www.mountainproject.com/route/271828/route-name,1,"Area > Crag","3,2",-1,Send,,Boulder,,10,20300
+2023-06-01,"Route Name",V1,,https://www.mountainproject.com/route/271828/route-name,1,"Area > Crag",,-1,Send,,Boulder,,10,20300
+2023-06-01,"Route Name",V1,,https://www.mountainproject.com/route/271828/route-name,1,"Area > Crag",-1,-1,Send,,Boulder,,10,20300
+"#;
+
+        let mut reader = csv::Reader::from_reader(mp_csv.as_bytes());
+        let stars = reader
+            .deserialize()
+            .map(|record| record.map(|r: MountainProjectTick| r.avg_stars))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        assert_eq!(stars, vec![Some(3.2), Some(3.2), None, None]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn snake_case_headers() -> Result<(), Box<dyn std::error::Error>> {
+        let mp_csv = "date,route,rating,notes,url,pitches,location,avg_stars,your_stars,style,lead_style,route_type,your_rating,length,rating_code
+2023-06-01,Route Name,5.9,,,1,Area > Crag,2.5,-1,Lead,Onsight,Trad,,80,20300
+";
+        let record: MountainProjectTick = csv::Reader::from_reader(mp_csv.as_bytes())
+            .deserialize()
+            .next()
+            .expect("one row")?;
+
+        assert_eq!(record.route, "Route Name");
+        assert_eq!(record.avg_stars, Some(2.5));
+        assert_eq!(record.lead_style, Some(MountainProjectLeadStyle::Onsight));
+        assert_eq!(record.rating_code, 20300);
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(clippy::zero_prefixed_literal)]
     fn from_csv() -> Result<(), Box<dyn std::error::Error>> {
         let mp_csv = r#"Date,Route,Rating,Notes,URL,Pitches,Location,"Avg Stars","Your Stars",Style,"Lead Style","Route Type","Your Rating",Length,"Rating Code"
 2023-06-01,"Route Name",V1,,https://www.mountainproject.com/route/271828/route-name,1,"Area > Crag",2.5,-1,Send,,Boulder,,10,20300
@@ -0,0 +1,22 @@
+//! Commonly used types and functions
+//!
+//! # Examples
+//! ```
+//! use open_tick::prelude::*;
+//!
+//! let grade = Grade::parse("5.10a");
+//! assert_eq!(grade.system(), Some(GradeSystem::Yds));
+//! ```
+
+pub use crate::grade::{Font, French, Grade, GradePreferences, GradeSystem, VScale, Yds};
+#[cfg(feature = "serde")]
+pub use crate::import::{collect_ticks, collect_ticks_lossy, parse_str, ImportOptions, IterExt};
+pub use crate::logbook::Logbook;
+#[cfg(feature = "serde")]
+pub use crate::IntoOpenTick;
+#[cfg(feature = "serde")]
+pub use crate::RouteRef;
+pub use crate::{
+    AscentStyle, Discipline, DisciplineKind, Location, MergePolicy, OpenTick, Outcome, PartialDate,
+    Source,
+};
@@ -0,0 +1,984 @@
+use crate::grade::{Grade, GradePreferences, GradeSystem};
+use crate::util::{self, normalize_name};
+use crate::{AscentStyle, DisciplineKind, Location, MergePolicy, OpenTick, Outcome, Source};
+use chrono::{Datelike, NaiveDate};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Key identifying a route across ticks
+///
+/// Built from the normalized route name and location, so that ascents of the same route logged
+/// with slightly different spelling still share a key.  The tick's
+/// [`route_url`](OpenTick::route_url) is part of the key when it has one, so that two routes of
+/// the same name at the same crag are kept apart.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RouteKey {
+    pub name: String,
+    pub location: String,
+    pub url: Option<String>,
+}
+
+impl RouteKey {
+    /// Key for the route of a tick, `None` if the tick has no route name
+    pub fn from_tick(tick: &OpenTick) -> Option<RouteKey> {
+        let name = normalize_name(tick.route_name.as_deref()?);
+        let location = tick
+            .route_location
+            .as_deref()
+            .map(normalize_name)
+            .unwrap_or_default();
+
+        Some(RouteKey {
+            name,
+            location,
+            url: tick.route_url.clone(),
+        })
+    }
+}
+
+/// Group ticks by route, giving the ascent history of each route
+///
+/// Ticks without a route name are skipped.  Within a route, ticks keep their order from `ticks`.
+pub fn group_by_route(ticks: &[OpenTick]) -> HashMap<RouteKey, Vec<&OpenTick>> {
+    let mut groups: HashMap<RouteKey, Vec<&OpenTick>> = HashMap::new();
+
+    for tick in ticks {
+        if let Some(key) = RouteKey::from_tick(tick) {
+            groups.entry(key).or_default().push(tick);
+        }
+    }
+
+    groups
+}
+
+/// The first send of each route
+///
+/// For each route, returns the earliest tick whose [`AscentStyle`](crate::AscentStyle) counts as
+/// a send, dropping prior attempts and later repeats.  Routes that were never sent are skipped,
+/// as are ticks without an ascent style.  The result is sorted by date, with undated ticks last.
+pub fn first_sends(ticks: &[OpenTick]) -> Vec<&OpenTick> {
+    let mut sends: Vec<&OpenTick> = group_by_route(ticks)
+        .into_values()
+        .filter_map(|ascents| {
+            ascents
+                .into_iter()
+                .filter(|t| t.ascent_style.is_some_and(|s| s.is_send()))
+                .min_by_key(|t| (t.date.is_none(), t.date))
+        })
+        .collect();
+
+    sends.sort_by_key(|t| (t.date.is_none(), t.date, t.route_name.clone()));
+
+    sends
+}
+
+/// The [outcome](OpenTick::outcome) of each tick, with sends of routes already sent as repeats
+///
+/// Mountain Project, unlike theCrag, doesn't mark repeats, so a single tick can't tell whether
+/// it was the first send.  Here, each route's sends after its first, in date order as for
+/// [`first_sends`], are [`Outcome::Repeat`].  Sends on the same day are ordered as in `ticks`.
+/// Ticks without a route name keep their own outcome.  The result is in the order of `ticks`.
+///
+/// # Examples
+/// ```
+/// use open_tick::stats::outcomes;
+/// use open_tick::{AscentStyle, OpenTick, Outcome};
+///
+/// let mut tick = OpenTick::default();
+/// tick.route_name = Some("The Nose".to_string());
+/// tick.ascent_style = Some(AscentStyle::Redpoint);
+///
+/// let ticks = [tick.clone(), tick];
+/// assert_eq!(outcomes(&ticks), [Some(Outcome::Send), Some(Outcome::Repeat)]);
+/// ```
+pub fn outcomes(ticks: &[OpenTick]) -> Vec<Option<Outcome>> {
+    let mut outcomes: Vec<Option<Outcome>> = ticks.iter().map(OpenTick::outcome).collect();
+
+    let mut order: Vec<usize> = (0..ticks.len()).collect();
+    order.sort_by_key(|&i| (ticks[i].date.is_none(), ticks[i].date));
+
+    let mut sent = HashSet::new();
+    for i in order {
+        let Some(outcome @ (Outcome::Send | Outcome::Repeat)) = outcomes[i] else {
+            continue;
+        };
+        let Some(key) = RouteKey::from_tick(&ticks[i]) else {
+            continue;
+        };
+        if !sent.insert(key) && outcome == Outcome::Send {
+            outcomes[i] = Some(Outcome::Repeat);
+        }
+    }
+
+    outcomes
+}
+
+/// Count ticks in each month, keyed by `(year, month)`
+///
+/// Undated ticks are skipped.
+pub fn ticks_per_month(ticks: &[OpenTick]) -> BTreeMap<(i32, u32), usize> {
+    let mut counts = BTreeMap::new();
+
+    for date in ticks.iter().filter_map(|t| t.date) {
+        *counts.entry((date.year(), date.month())).or_default() += 1;
+    }
+
+    counts
+}
+
+/// Number of distinct days on which ticks were logged, as opposed to the number of ascents
+///
+/// Undated ticks are skipped, as are ticks with only a [partial date](OpenTick::partial_date).
+pub fn days_climbed(ticks: &[OpenTick]) -> usize {
+    ticks
+        .iter()
+        .filter_map(|t| t.date)
+        .collect::<BTreeSet<NaiveDate>>()
+        .len()
+}
+
+/// Fraction of sends that were onsights or flashes
+///
+/// Only first sends count; repeats, attempts and ticks without an ascent style are skipped.
+/// Returns 0 if there are no sends.
+pub fn onsight_flash_ratio(ticks: &[OpenTick]) -> f64 {
+    let sends: Vec<&OpenTick> = ticks
+        .iter()
+        .filter(|t| t.outcome() == Some(Outcome::Send))
+        .collect();
+
+    if sends.is_empty() {
+        return 0.0;
+    }
+
+    let first_tries = sends
+        .iter()
+        .filter(|t| {
+            matches!(
+                t.ascent_style,
+                Some(AscentStyle::Onsight | AscentStyle::Flash)
+            )
+        })
+        .count();
+
+    first_tries as f64 / sends.len() as f64
+}
+
+/// Fraction of ascents done without partners
+///
+/// Bouldering and deep water solo are usually climbed without a partner, so ticks whose
+/// [primary](crate::Discipline::primary) discipline is one of them are skipped; ticks of unknown
+/// discipline count.  A tick with no partners recorded counts as climbed alone, so ticks from
+/// platforms that don't record partners, such as Mountain Project, are best filtered out first.
+/// Returns 0 if no tick counts.
+pub fn solo_ratio(ticks: &[OpenTick]) -> f64 {
+    let roped: Vec<&OpenTick> = ticks
+        .iter()
+        .filter(|t| {
+            let primary = t
+                .ascent_discipline
+                .as_ref()
+                .or(t.route_discipline.as_ref())
+                .and_then(|d| d.primary());
+            !matches!(
+                primary,
+                Some(DisciplineKind::Bouldering | DisciplineKind::DeepWaterSolo)
+            )
+        })
+        .collect();
+
+    if roped.is_empty() {
+        return 0.0;
+    }
+
+    let alone = roped.iter().filter(|t| t.partner_count() == 0).count();
+
+    alone as f64 / roped.len() as f64
+}
+
+/// Count ticks in each discipline
+///
+/// Uses the discipline of the ascent if known, otherwise that of the route.  A tick in several
+/// disciplines, such as a route that is both sport and trad, counts towards each.
+pub fn discipline_counts(ticks: &[OpenTick]) -> BTreeMap<DisciplineKind, usize> {
+    let mut counts = BTreeMap::new();
+
+    for discipline in ticks
+        .iter()
+        .filter_map(|t| t.ascent_discipline.as_ref().or(t.route_discipline.as_ref()))
+    {
+        for kind in discipline.iter() {
+            *counts.entry(kind).or_default() += 1;
+        }
+    }
+
+    counts
+}
+
+/// Which disciplines a climb in several counts towards
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MultiDiscipline {
+    /// Count the climb in each of its disciplines
+    #[default]
+    All,
+    /// Count the climb only in its [primary](crate::Discipline::primary) discipline
+    Primary,
+}
+
+/// Count sends at each grade, for each discipline
+///
+/// Keys are disciplines, then the [ordinal](Grade::ordinal) of the route grade in the system
+/// `preferences` gives for the discipline.  Disciplines are taken as for [`discipline_counts`],
+/// and `multi` decides whether a climb in several counts in each or only once.  Ticks that
+/// aren't sends, have no discipline, or whose grade isn't recognized or has no equivalent in the
+/// preferred system, are skipped.
+pub fn pyramid(
+    ticks: &[OpenTick],
+    preferences: &GradePreferences,
+    multi: MultiDiscipline,
+) -> BTreeMap<DisciplineKind, BTreeMap<u32, usize>> {
+    let mut counts: BTreeMap<DisciplineKind, BTreeMap<u32, usize>> = BTreeMap::new();
+
+    for tick in ticks
+        .iter()
+        .filter(|t| t.ascent_style.is_some_and(|s| s.is_send()))
+    {
+        let (Some(discipline), Some(grade)) = (
+            tick.ascent_discipline
+                .as_ref()
+                .or(tick.route_discipline.as_ref()),
+            tick.recognized_route_grade(),
+        ) else {
+            continue;
+        };
+
+        let kinds: Vec<DisciplineKind> = match multi {
+            MultiDiscipline::All => discipline.iter().collect(),
+            MultiDiscipline::Primary => discipline.primary().into_iter().collect(),
+        };
+
+        for kind in kinds {
+            let ordinal = grade
+                .to_system(preferences.system_for(kind))
+                .ok()
+                .and_then(|g| g.ordinal());
+            if let Some(ordinal) = ordinal {
+                *counts.entry(kind).or_default().entry(ordinal).or_default() += 1;
+            }
+        }
+    }
+
+    counts
+}
+
+/// Collapse multi-pitch climbs logged one row per pitch into a single tick
+///
+/// Mountain Project users sometimes log each pitch separately, which inflates tick counts.  A
+/// row is taken to be one pitch of a climb if it records its pitches and its comment names the
+/// pitch, as by [`util::parse_pitch_number`].  Pitch rows of the same route on the same day are
+/// merged into the first of them, as by [`OpenTick::merge`] with [`MergePolicy::Concatenate`],
+/// and their pitches summed.  A pitch named again starts another climb of the route.  Other
+/// ticks, such as two laps of a single-pitch route, are kept as they are.  The result keeps the
+/// order of `ticks`.
+pub fn collapse_multipitch(ticks: &[OpenTick]) -> Vec<OpenTick> {
+    let mut collapsed: Vec<OpenTick> = Vec::with_capacity(ticks.len());
+    // Index in `collapsed` of each climb so far, and the pitches it has
+    let mut climbs: HashMap<(RouteKey, NaiveDate), (usize, BTreeSet<u32>)> = HashMap::new();
+
+    for tick in ticks {
+        let pitch = tick
+            .comment
+            .as_deref()
+            .and_then(util::parse_pitch_number)
+            .filter(|_| tick.pitches.is_some());
+        let key = tick.date.zip(RouteKey::from_tick(tick));
+
+        let (Some(pitch), Some((date, route))) = (pitch, key) else {
+            collapsed.push(tick.clone());
+            continue;
+        };
+
+        let climb = climbs
+            .get_mut(&(route.clone(), date))
+            .filter(|(_, pitches)| !pitches.contains(&pitch));
+
+        match climb {
+            Some((i, pitches)) => {
+                pitches.insert(pitch);
+                let first = std::mem::take(&mut collapsed[*i]);
+                let pitches = first
+                    .pitches
+                    .zip(tick.pitches)
+                    .map(|(a, b)| a.saturating_add(b));
+                collapsed[*i] = OpenTick {
+                    pitches,
+                    ..first.merge(tick.clone(), MergePolicy::Concatenate)
+                };
+            }
+            None => {
+                climbs.insert((route, date), (collapsed.len(), BTreeSet::from([pitch])));
+                collapsed.push(tick.clone());
+            }
+        }
+    }
+
+    collapsed
+}
+
+/// How to round an average star rating
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StarRounding {
+    /// Don't round
+    #[default]
+    Exact,
+    /// Round to the nearest half star, as star ratings are usually shown
+    NearestHalf,
+}
+
+/// Average star rating of the routes ticked
+///
+/// Ticks without a star rating, or with a negative one marking an unrated route, are skipped, and
+/// `None` is returned if no tick has one.  Ratings are averaged as recorded, so mixing sources that rate on different scales skews the result;
+/// see [`OpenTick::route_stars`].
+pub fn average_stars(ticks: &[OpenTick], rounding: StarRounding) -> Option<f32> {
+    let stars: Vec<f32> = ticks
+        .iter()
+        .filter_map(|t| t.route_stars.filter(|&s| s >= 0.0))
+        .collect();
+
+    if stars.is_empty() {
+        return None;
+    }
+
+    let average = stars.iter().sum::<f32>() / stars.len() as f32;
+
+    Some(match rounding {
+        StarRounding::Exact => average,
+        StarRounding::NearestHalf => (average * 2.0).round() / 2.0,
+    })
+}
+
+/// Distinct crags at which ticks were logged
+///
+/// Uses the crag of the parsed [`OpenTick::location`] if known, otherwise the last area of
+/// [`OpenTick::route_location`].  Ticks with neither are skipped.  Filter `ticks` by date first
+/// to count crags visited in a year or season.
+pub fn crags_visited(ticks: &[OpenTick]) -> BTreeSet<String> {
+    ticks.iter().filter_map(crag).collect()
+}
+
+/// Average [grade offset](OpenTick::grade_offset) of the ticks at each crag
+///
+/// Positive if the crag's routes felt hard for their grades, i.e. are sandbagged, and negative
+/// if they felt soft.  Crags are found as for [`crags_visited`].  Ticks without a crag or an
+/// offset are skipped, and crags without any offset are left out.
+pub fn grade_offset_by_crag(ticks: &[OpenTick]) -> BTreeMap<String, f32> {
+    let mut offsets: BTreeMap<String, Vec<f32>> = BTreeMap::new();
+
+    for tick in ticks {
+        if let (Some(crag), Some(offset)) = (crag(tick), tick.grade_offset()) {
+            offsets.entry(crag).or_default().push(offset);
+        }
+    }
+
+    offsets
+        .into_iter()
+        .map(|(crag, offsets)| {
+            let average = offsets.iter().sum::<f32>() / offsets.len() as f32;
+            (crag, average)
+        })
+        .collect()
+}
+
+/// The crag of the parsed location if known, otherwise the last area of the route's location
+fn crag(tick: &OpenTick) -> Option<String> {
+    tick.location
+        .as_ref()
+        .and_then(|l| l.crag.clone())
+        .or_else(|| Location::from_path(tick.route_location.as_deref()?).crag)
+}
+
+/// Average quality of the routes ticked, on a scale of 0 to 5 stars
+///
+/// Unlike [`average_stars`], each rating is first scaled from its platform's scale, so ticks from
+/// Mountain Project (0 to 4 stars) and theCrag can be averaged together.  theCrag ticks are rated
+/// by their [quality percentage](OpenTick::route_quality), falling back to their count of stars
+/// (0 to 5).  Unrated routes are skipped, as are ticks from other platforms or of unknown
+/// source.  `None` if no tick has a rating.
+pub fn average_quality(ticks: &[OpenTick]) -> Option<f32> {
+    let ratings: Vec<f32> = ticks
+        .iter()
+        .filter_map(|t| {
+            let stars = t.route_stars.filter(|&s| s >= 0.0);
+            match t.source? {
+                Source::MountainProject => Some(stars? / 4.0 * 5.0),
+                Source::TheCrag => t.route_quality.map(|q| q / 20.0).or(stars),
+                _ => None,
+            }
+        })
+        .collect();
+
+    if ratings.is_empty() {
+        return None;
+    }
+
+    Some(ratings.iter().sum::<f32>() / ratings.len() as f32)
+}
+
+/// Grade-weighted volume of sends, a measure of training load
+///
+/// Each send weighs one more than the [ordinal](crate::grade::Grade::ordinal) of its route's
+/// grade in `system`, so the easiest grade in the system weighs 1 and each grade harder adds 1.
+/// A range weighs the average of its bounds.  Sends whose grade isn't recognized or has no
+/// equivalent in `system` are skipped, as are ticks that aren't sends.
+pub fn volume(ticks: &[OpenTick], system: GradeSystem) -> f64 {
+    ticks
+        .iter()
+        .filter(|t| t.ascent_style.is_some_and(|s| s.is_send()))
+        .filter_map(|t| {
+            let grade = t.recognized_route_grade()?.to_system(system).ok()?;
+            let (_, low, high) = grade.bounds()?;
+            Some(f64::from(low + high) / 2.0 + 1.0)
+        })
+        .sum()
+}
+
+/// Hardest grade sent in one of the given styles, such as the onsight or redpoint limit
+///
+/// Route grades are converted to `system`, and ticks whose grades can't be are skipped.  With
+/// `since`, only ticks on or after that date count, and undated ticks are skipped.  `None` if no
+/// tick qualifies.
+pub fn limit_grade(
+    ticks: &[OpenTick],
+    system: GradeSystem,
+    styles: &[AscentStyle],
+    since: Option<NaiveDate>,
+) -> Option<Grade> {
+    ticks
+        .iter()
+        .filter(|t| t.ascent_style.is_some_and(|s| styles.contains(&s)))
+        .filter(|t| since.is_none_or(|since| t.date.is_some_and(|d| d >= since)))
+        .filter_map(|t| t.recognized_route_grade()?.to_system(system).ok())
+        .max_by(|a, b| a.cmp_same_system(b).unwrap_or(Ordering::Equal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Discipline;
+    use chrono::NaiveDate;
+    use pretty_assertions::assert_eq;
+
+    fn tick(date: (i32, u32, u32), name: &str, location: &str) -> OpenTick {
+        OpenTick {
+            date: NaiveDate::from_ymd_opt(date.0, date.1, date.2),
+            route_name: Some(name.to_string()),
+            route_location: Some(location.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn group_repeat_ascents() {
+        let ticks = vec![
+            tick((2023, 1, 1), "The Nose", "El Capitan"),
+            tick((2023, 1, 2), "Other Route", "El Capitan"),
+            tick((2023, 1, 3), "Nose, The", "El Capitan"),
+        ];
+
+        let groups = group_by_route(&ticks);
+
+        assert_eq!(groups.len(), 2);
+
+        let nose = &groups[&RouteKey {
+            name: "the nose".to_string(),
+            location: "el capitan".to_string(),
+            url: None,
+        }];
+        assert_eq!(nose.len(), 2);
+        assert_eq!(nose[0].date, NaiveDate::from_ymd_opt(2023, 1, 1));
+        assert_eq!(nose[1].date, NaiveDate::from_ymd_opt(2023, 1, 3));
+    }
+
+    #[test]
+    fn same_name_different_route_url() {
+        let with_url = |url: &str| OpenTick {
+            route_url: Some(url.to_string()),
+            ..tick((2023, 1, 1), "Arete", "Crag")
+        };
+        let ticks = vec![
+            with_url("https://www.mountainproject.com/route/105748391/arete"),
+            with_url("https://www.mountainproject.com/route/105748392/arete"),
+            with_url("https://www.mountainproject.com/route/105748391/arete"),
+        ];
+
+        let groups = group_by_route(&ticks);
+
+        assert_eq!(groups.len(), 2);
+        assert!(groups.values().any(|ascents| ascents.len() == 2));
+    }
+
+    #[test]
+    fn mountain_project_repeats() {
+        let ascent = |date, style| OpenTick {
+            ascent_style: Some(style),
+            source: Some(Source::MountainProject),
+            ..tick(date, "Project", "Crag")
+        };
+        let ticks = vec![
+            ascent((2023, 3, 1), AscentStyle::Send),
+            ascent((2023, 1, 1), AscentStyle::Attempt),
+            ascent((2023, 2, 1), AscentStyle::Redpoint),
+            ascent((2023, 3, 1), AscentStyle::TopRope),
+            OpenTick {
+                ascent_style: Some(AscentStyle::Send),
+                ..tick((2023, 3, 1), "Other Route", "Crag")
+            },
+        ];
+
+        assert_eq!(
+            outcomes(&ticks),
+            [
+                Some(Outcome::Repeat),
+                Some(Outcome::Attempt),
+                Some(Outcome::Send),
+                Some(Outcome::Unknown),
+                Some(Outcome::Send),
+            ]
+        );
+    }
+
+    #[test]
+    fn first_send_of_project() {
+        let ticks = vec![
+            OpenTick {
+                ascent_style: Some(AscentStyle::Attempt),
+                ..tick((2023, 1, 1), "Project", "Crag")
+            },
+            OpenTick {
+                ascent_style: Some(AscentStyle::Repeat),
+                ..tick((2023, 3, 1), "Project", "Crag")
+            },
+            OpenTick {
+                ascent_style: Some(AscentStyle::Redpoint),
+                ..tick((2023, 2, 1), "Project", "Crag")
+            },
+            OpenTick {
+                ascent_style: Some(AscentStyle::Attempt),
+                ..tick((2023, 2, 1), "Never Sent", "Crag")
+            },
+        ];
+
+        let sends = first_sends(&ticks);
+
+        assert_eq!(sends.len(), 1);
+        assert_eq!(sends[0].ascent_style, Some(AscentStyle::Redpoint));
+        assert_eq!(sends[0].date, NaiveDate::from_ymd_opt(2023, 2, 1));
+    }
+
+    #[test]
+    fn count_per_month() {
+        let ticks = vec![
+            tick((2023, 5, 1), "A", "Crag"),
+            tick((2023, 5, 31), "B", "Crag"),
+            tick((2023, 6, 1), "C", "Crag"),
+            OpenTick::default(),
+        ];
+
+        assert_eq!(
+            ticks_per_month(&ticks),
+            BTreeMap::from([((2023, 5), 2), ((2023, 6), 1)])
+        );
+    }
+
+    #[test]
+    fn days_out() {
+        let ticks = vec![
+            tick((2023, 5, 1), "A", "Crag"),
+            tick((2023, 5, 1), "B", "Crag"),
+            tick((2023, 5, 3), "A", "Crag"),
+            OpenTick::default(),
+        ];
+
+        assert_eq!(days_climbed(&ticks), 2);
+        assert_eq!(days_climbed(&[]), 0);
+    }
+
+    #[test]
+    fn same_name_different_location() {
+        let ticks = vec![
+            tick((2023, 1, 1), "Classic", "Crag A"),
+            tick((2023, 1, 1), "Classic", "Crag B"),
+        ];
+
+        assert_eq!(group_by_route(&ticks).len(), 2);
+    }
+
+    #[test]
+    fn onsight_flash_share() {
+        let styles = [
+            AscentStyle::Onsight,
+            AscentStyle::Flash,
+            AscentStyle::Flash,
+            AscentStyle::Redpoint,
+            AscentStyle::Attempt,
+            AscentStyle::Repeat,
+        ];
+        let ticks: Vec<OpenTick> = styles
+            .into_iter()
+            .map(|style| OpenTick {
+                ascent_style: Some(style),
+                ..tick((2023, 1, 1), "Route", "Crag")
+            })
+            .collect();
+
+        assert_eq!(onsight_flash_ratio(&ticks), 0.75);
+        assert_eq!(onsight_flash_ratio(&[]), 0.0);
+    }
+
+    #[test]
+    fn solo_share() {
+        let ascent = |discipline: &str, partners: &[&str]| OpenTick {
+            route_discipline: Some(discipline.parse().unwrap()),
+            partners: (!partners.is_empty())
+                .then(|| partners.iter().map(|p| p.to_string()).collect()),
+            ..Default::default()
+        };
+        let ticks = vec![
+            ascent("trad", &["Jo Bloggs", "Sam"]),
+            ascent("sport", &["Sam"]),
+            ascent("sport", &[]),
+            ascent("alpine", &[]),
+            ascent("boulder", &[]),
+            ascent("dws", &["Sam"]),
+        ];
+
+        assert_eq!(ticks[0].partner_count(), 2);
+        assert_eq!(solo_ratio(&ticks), 0.5);
+        assert_eq!(solo_ratio(&ticks[4..]), 0.0);
+    }
+
+    #[test]
+    fn collapse_pitch_rows() {
+        let pitch = |n: u16| OpenTick {
+            pitches: Some(1),
+            comment: Some(format!("Pitch {n}")),
+            ..tick((2023, 7, 1), "Long Route", "Big Wall")
+        };
+        let ticks = vec![
+            pitch(1),
+            tick((2023, 7, 1), "Single", "Big Wall"),
+            pitch(2),
+            pitch(3),
+        ];
+
+        let collapsed = collapse_multipitch(&ticks);
+
+        assert_eq!(collapsed.len(), 2);
+        assert_eq!(collapsed[0].pitches, Some(3));
+        assert_eq!(
+            collapsed[0].comment.as_deref(),
+            Some("Pitch 1\nPitch 2\nPitch 3")
+        );
+        assert_eq!(collapsed[1].route_name.as_deref(), Some("Single"));
+    }
+
+    #[test]
+    fn single_pitch_laps_kept() {
+        let ascent = |style| OpenTick {
+            pitches: Some(1),
+            ascent_style: Some(style),
+            comment: Some("Fell at the crux".to_string()),
+            ..tick((2023, 7, 1), "Short Route", "Crag")
+        };
+        let ticks = vec![ascent(AscentStyle::Attempt), ascent(AscentStyle::Redpoint)];
+
+        let collapsed = collapse_multipitch(&ticks);
+
+        assert_eq!(collapsed.len(), 2);
+        assert_eq!(collapsed[1].ascent_style, Some(AscentStyle::Redpoint));
+    }
+
+    #[test]
+    fn repeated_pitch_starts_another_climb() {
+        let pitch = |n: u16| OpenTick {
+            pitches: Some(1),
+            comment: Some(format!("P{n}")),
+            ..tick((2023, 7, 1), "Long Route", "Big Wall")
+        };
+        let ticks = vec![pitch(1), pitch(2), pitch(1), pitch(2)];
+
+        let collapsed = collapse_multipitch(&ticks);
+
+        assert_eq!(collapsed.len(), 2);
+        assert!(collapsed.iter().all(|t| t.pitches == Some(2)));
+    }
+
+    #[test]
+    fn average_star_rating() {
+        let ticks: Vec<OpenTick> = [Some(3.0), Some(2.2), None, Some(1.5), Some(-1.0)]
+            .into_iter()
+            .map(|route_stars| OpenTick {
+                route_stars,
+                ..Default::default()
+            })
+            .collect();
+
+        assert_eq!(average_stars(&ticks, StarRounding::Exact), Some(6.7 / 3.0));
+        assert_eq!(average_stars(&ticks, StarRounding::NearestHalf), Some(2.0));
+        assert_eq!(average_stars(&ticks[2..3], StarRounding::Exact), None);
+        assert_eq!(average_stars(&ticks[4..], StarRounding::Exact), None);
+    }
+
+    #[test]
+    fn average_quality_across_sources() {
+        let rated = |source, route_stars| OpenTick {
+            source: Some(source),
+            route_stars,
+            ..Default::default()
+        };
+        let ticks = vec![
+            rated(Source::MountainProject, Some(4.0)),
+            rated(Source::MountainProject, Some(-1.0)),
+            rated(Source::TheCrag, Some(2.5)),
+            rated(Source::TheCrag, None),
+            rated(Source::VerticalLife, Some(1.0)),
+        ];
+
+        assert_eq!(average_quality(&ticks), Some(3.75));
+        assert_eq!(average_quality(&ticks[1..2]), None);
+    }
+
+    #[test]
+    fn average_quality_top_of_scale() {
+        let mountain_project = OpenTick {
+            source: Some(Source::MountainProject),
+            route_stars: Some(4.0),
+            ..Default::default()
+        };
+        let crag_stars = OpenTick {
+            source: Some(Source::TheCrag),
+            route_stars: Some(5.0),
+            ..Default::default()
+        };
+        let crag_quality = OpenTick {
+            source: Some(Source::TheCrag),
+            route_stars: Some(1.0),
+            route_quality: Some(100.0),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            average_quality(&[mountain_project, crag_stars, crag_quality]),
+            Some(5.0)
+        );
+    }
+
+    #[test]
+    fn alpine_counted_separately() {
+        let ticks: Vec<OpenTick> = ["alpine", "trad", "Alpine, Trad"]
+            .into_iter()
+            .map(|d| OpenTick {
+                route_discipline: Some(d.parse().unwrap()),
+                ..Default::default()
+            })
+            .collect();
+
+        assert_eq!(
+            discipline_counts(&ticks),
+            BTreeMap::from([(DisciplineKind::Alpine, 2), (DisciplineKind::Trad, 2)])
+        );
+    }
+
+    #[test]
+    fn count_per_discipline() {
+        let sport_trad = Discipline {
+            sport: true,
+            trad: true,
+            ..Default::default()
+        };
+        let ticks = vec![
+            OpenTick {
+                route_discipline: Some(sport_trad.clone()),
+                ..Default::default()
+            },
+            OpenTick {
+                route_discipline: Some(sport_trad),
+                ascent_discipline: Some(Discipline {
+                    trad: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            OpenTick {
+                route_discipline: Some(Discipline {
+                    bouldering: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            OpenTick::default(),
+        ];
+
+        assert_eq!(
+            discipline_counts(&ticks),
+            BTreeMap::from([
+                (DisciplineKind::Bouldering, 1),
+                (DisciplineKind::Sport, 1),
+                (DisciplineKind::Trad, 2),
+            ])
+        );
+    }
+
+    #[test]
+    fn pyramid_multi_discipline_policy() {
+        let send = |grade: &str, discipline: &str| OpenTick {
+            route_grade: Some(grade.to_string()),
+            route_discipline: Some(discipline.parse().unwrap()),
+            ascent_style: Some(AscentStyle::Redpoint),
+            ..Default::default()
+        };
+        let ticks = vec![
+            send("5.10a", "sport, trad"),
+            send("5.10a", "sport"),
+            send("V3", "boulder"),
+            OpenTick {
+                ascent_style: Some(AscentStyle::Attempt),
+                ..send("5.12a", "sport")
+            },
+        ];
+        let preferences = GradePreferences::default();
+
+        assert_eq!(
+            pyramid(&ticks, &preferences, MultiDiscipline::All),
+            BTreeMap::from([
+                (DisciplineKind::Bouldering, BTreeMap::from([(4, 1)])),
+                (DisciplineKind::Sport, BTreeMap::from([(10, 2)])),
+                (DisciplineKind::Trad, BTreeMap::from([(10, 1)])),
+            ])
+        );
+        assert_eq!(
+            pyramid(&ticks, &preferences, MultiDiscipline::Primary),
+            BTreeMap::from([
+                (DisciplineKind::Bouldering, BTreeMap::from([(4, 1)])),
+                (DisciplineKind::Sport, BTreeMap::from([(10, 1)])),
+                (DisciplineKind::Trad, BTreeMap::from([(10, 1)])),
+            ])
+        );
+    }
+
+    #[test]
+    fn crags_counted_once() {
+        let ticks = vec![
+            tick(
+                (2023, 1, 1),
+                "The Nose",
+                "California > Yosemite > El Capitan",
+            ),
+            tick(
+                (2023, 1, 2),
+                "Salathé Wall",
+                "California > Yosemite > El Capitan",
+            ),
+            OpenTick {
+                location: Some(Location::from_path("Victoria > Arapiles")),
+                ..tick((2023, 2, 1), "Syzygy", "")
+            },
+            OpenTick::default(),
+        ];
+
+        assert_eq!(
+            crags_visited(&ticks).into_iter().collect::<Vec<_>>(),
+            ["Arapiles", "El Capitan"]
+        );
+    }
+
+    #[test]
+    fn sandbagged_crag() {
+        let ascent = |crag: &str, route_grade: &str, ascent_grade: &str| OpenTick {
+            route_grade: Some(route_grade.to_string()),
+            ascent_grade: Some(ascent_grade.to_string()),
+            ..tick((2023, 1, 1), "Route", crag)
+        };
+        let ticks = vec![
+            ascent("Area > Hard Crag", "5.10a", "5.10c"),
+            ascent("Area > Hard Crag", "5.9 R", "5.10a"),
+            ascent("Area > Soft Crag", "6a+", "6a"),
+            ascent("Area > Soft Crag", "6a", "5.9"),
+            ascent("Area > Ungraded", "6a", ""),
+        ];
+
+        assert_eq!(
+            grade_offset_by_crag(&ticks),
+            BTreeMap::from([
+                ("Hard Crag".to_string(), 1.5),
+                ("Soft Crag".to_string(), -1.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn grade_weighted_volume() {
+        let send = |grade: &str, style| OpenTick {
+            route_grade: Some(grade.to_string()),
+            ascent_style: Some(style),
+            ..Default::default()
+        };
+        let ticks = vec![
+            send("5.0", AscentStyle::Onsight),
+            send("5.10a", AscentStyle::Redpoint),
+            send("5.10a/b", AscentStyle::Flash),
+            send("5.12a", AscentStyle::Attempt),
+            send("V3", AscentStyle::Flash),
+            send("6a", AscentStyle::Redpoint),
+        ];
+
+        assert_eq!(volume(&ticks, GradeSystem::Yds), 1.0 + 11.0 + 11.5 + 11.0);
+        assert_eq!(volume(&ticks, GradeSystem::VScale), 5.0);
+        assert_eq!(volume(&[], GradeSystem::Yds), 0.0);
+    }
+
+    #[test]
+    fn onsight_and_redpoint_limits() {
+        let ascent = |day, grade: &str, style| OpenTick {
+            date: NaiveDate::from_ymd_opt(2023, 6, day),
+            route_grade: Some(grade.to_string()),
+            ascent_style: Some(style),
+            ..Default::default()
+        };
+        let ticks = vec![
+            ascent(1, "7a", AscentStyle::Onsight),
+            ascent(2, "5.11b", AscentStyle::Onsight),
+            ascent(3, "7b+", AscentStyle::Redpoint),
+            ascent(4, "7c", AscentStyle::Attempt),
+            ascent(5, "6c+", AscentStyle::Flash),
+            ascent(6, "V5", AscentStyle::Onsight),
+        ];
+        let onsight = [AscentStyle::Onsight];
+        let redpoint = [AscentStyle::Redpoint, AscentStyle::Pinkpoint];
+
+        assert_eq!(
+            limit_grade(&ticks, GradeSystem::French, &onsight, None),
+            Some(Grade::parse("7a"))
+        );
+        assert_eq!(
+            limit_grade(&ticks, GradeSystem::French, &redpoint, None),
+            Some(Grade::parse("7b+"))
+        );
+        assert_eq!(
+            limit_grade(
+                &ticks,
+                GradeSystem::French,
+                &onsight,
+                NaiveDate::from_ymd_opt(2023, 6, 2)
+            ),
+            Some(Grade::parse("6c"))
+        );
+        assert_eq!(
+            limit_grade(&ticks, GradeSystem::VScale, &redpoint, None),
+            None
+        );
+    }
+}
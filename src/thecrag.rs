@@ -1,10 +1,16 @@
-use chrono::{DateTime, Utc};
+use crate::de::{or_other, parse_or_other, OrOther, Unrecognized};
+use crate::grade::Grade;
+use crate::{ConversionError, OpenTick};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
 // use std::convert::TryFrom;
 use url::Url;
 
 /// A tick as recorded in an export from
 /// `https://www.thecrag.com/climber/<username>/logbook-csv`
-#[derive(Debug, serde::Deserialize)]
+///
+/// Exports occasionally have blank or malformed links; blank links read as `None` and malformed
+/// ones are kept as [`TheCragLink::Malformed`] rather than failing the row.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct TheCragTick {
     #[serde(rename = "Route Name")]
     pub route_name: String,
@@ -12,13 +18,14 @@ pub struct TheCragTick {
     #[serde(rename = "Ascent Label")]
     pub ascent_label: String,
 
+    /// Optional because ticks that weren't exported from theCrag don't have one
     #[serde(rename = "Ascent ID")]
-    pub ascent_id: TheCragAscentId,
+    pub ascent_id: Option<TheCragAscentId>,
 
-    #[serde(rename = "Ascent Link")]
-    pub ascent_link: Url,
+    #[serde(rename = "Ascent Link", deserialize_with = "link")]
+    pub ascent_link: Option<TheCragLink>,
 
-    #[serde(rename = "Ascent Type")]
+    #[serde(rename = "Ascent Type", deserialize_with = "or_other")]
     pub ascent_type: TheCragAscentType,
 
     /// the route grade as recorded in theCrag
@@ -30,11 +37,11 @@ pub struct TheCragTick {
     pub ascent_grade: String,
 
     /// the gear style as recorded in theCrag
-    #[serde(rename = "Route Gear Style")]
+    #[serde(rename = "Route Gear Style", deserialize_with = "or_other")]
     pub route_gear_style: TheCragGearStyle,
 
     /// the gear style as climbed
-    #[serde(rename = "Ascent Gear Style")]
+    #[serde(rename = "Ascent Gear Style", deserialize_with = "or_other")]
     pub ascent_gear_style: TheCragGearStyle,
 
     /// height in meters
@@ -54,24 +61,24 @@ pub struct TheCragTick {
     pub route_stars: String,
 
     #[serde(rename = "Route ID")]
-    pub route_id: TheCragRouteId,
+    pub route_id: Option<TheCragRouteId>,
 
-    #[serde(rename = "Route Link")]
-    pub route_link: Url,
+    #[serde(rename = "Route Link", deserialize_with = "link")]
+    pub route_link: Option<TheCragLink>,
 
     #[serde(rename = "Country")]
     pub country: String,
 
     /// url of parent country in theCrag
-    #[serde(rename = "Country Link")]
-    pub country_link: Url,
+    #[serde(rename = "Country Link", deserialize_with = "link")]
+    pub country_link: Option<TheCragLink>,
 
     #[serde(rename = "Crag Name")]
     pub crag_name: String,
 
     /// url of crag in theCrag
-    #[serde(rename = "Crag Link")]
-    pub crag_link: Url,
+    #[serde(rename = "Crag Link", deserialize_with = "link")]
+    pub crag_link: Option<TheCragLink>,
 
     /// hierarchy of areas above route
     #[serde(rename = "Crag Path")]
@@ -91,16 +98,207 @@ pub struct TheCragTick {
     #[serde(rename = "Ascent Date")]
     pub ascent_date: Option<DateTime<Utc>>,
 
+    /// Optional because ticks that weren't exported from theCrag don't have one
     #[serde(rename = "Log Date")]
-    pub log_date: DateTime<Utc>,
+    pub log_date: Option<DateTime<Utc>>,
 
     #[serde(rename = "Shot")]
     pub shot: Option<u16>,
 }
 
+impl TheCragTick {
+    /// Date of the ascent in the given timezone
+    ///
+    /// theCrag records ascent times in UTC, so the UTC date can be a day off from the date at the
+    /// crag.  theCrag doesn't record the crag's timezone; callers must supply it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use open_tick::TheCragTick;
+    /// use chrono::FixedOffset;
+    ///
+    /// fn date_in_california(tick: &TheCragTick) -> Option<chrono::NaiveDate> {
+    ///     let pdt = FixedOffset::west_opt(7 * 3600).expect("valid offset");
+    ///     tick.local_ascent_date(&pdt)
+    /// }
+    /// ```
+    pub fn local_ascent_date<Tz: TimeZone>(&self, tz: &Tz) -> Option<NaiveDate> {
+        self.ascent_date.map(|d| d.with_timezone(tz).date_naive())
+    }
+
+    /// Convert to an [`OpenTick`], taking the date in the given timezone
+    ///
+    /// See [`TheCragTick::local_ascent_date`].
+    pub fn into_open_tick_in<Tz: TimeZone>(self, tz: &Tz) -> Result<OpenTick, ConversionError> {
+        let date = self.local_ascent_date(tz);
+        let mut tick = OpenTick::try_from(self)?;
+        tick.date = date;
+
+        Ok(tick)
+    }
+}
+
+impl TheCragTick {
+    /// Parts of the combined ascent label, such as `Red point of Route Name 24`
+    ///
+    /// Older exports sometimes fill in the label but leave some of the other columns blank, so
+    /// the label is a fallback for the route name, grade and ascent type.  `None` if the label is
+    /// blank.
+    ///
+    /// # Examples
+    /// ```
+    /// use open_tick::thecrag::{TheCragAscentLabel, TheCragAscentType};
+    ///
+    /// let label = TheCragAscentLabel::parse("Red point of Route Name 24").unwrap();
+    /// assert_eq!(label.ascent_type, Some(TheCragAscentType::RedPoint));
+    /// assert_eq!(label.route_name, "Route Name");
+    /// assert_eq!(label.grade.as_deref(), Some("24"));
+    /// ```
+    pub fn parsed_ascent_label(&self) -> Option<TheCragAscentLabel> {
+        TheCragAscentLabel::parse(&self.ascent_label)
+    }
+
+    /// The quality rating, as a percentage
+    ///
+    /// theCrag rates the quality of a route as a percentage, such as `83%`.  `None` if the quality
+    /// is blank or isn't a percentage from 0 to 100.
+    pub fn quality_percent(&self) -> Option<f32> {
+        let percent: f32 = self
+            .quality
+            .trim()
+            .strip_suffix('%')?
+            .trim_end()
+            .parse()
+            .ok()?;
+
+        (0.0..=100.0).contains(&percent).then_some(percent)
+    }
+
+    /// The quality rating, on a scale of 0 to 5 stars
+    ///
+    /// This is the route's quality, from [`TheCragTick::quality_percent`] as kept in
+    /// [`OpenTick::route_quality`], and is independent of the `Route Stars` column kept in
+    /// [`OpenTick::route_stars`].
+    pub fn quality_stars(&self) -> Option<f32> {
+        self.quality_percent().map(|percent| percent / 20.0)
+    }
+
+    /// The `Route Stars` column, as a count of stars from 0 to 5
+    ///
+    /// This is the count of stars shown for the route, not the
+    /// [quality percentage](TheCragTick::quality_percent).  `None` if the column is blank or isn't
+    /// a whole number from 0 to 5.
+    pub fn route_stars_count(&self) -> Option<u8> {
+        self.route_stars
+            .trim()
+            .parse()
+            .ok()
+            .filter(|stars| *stars <= 5)
+    }
+
+    /// Warnings for links that aren't valid URLs, naming the field and its text
+    pub(crate) fn malformed_links(&self) -> Vec<String> {
+        [
+            ("ascent_link", &self.ascent_link),
+            ("route_link", &self.route_link),
+            ("country_link", &self.country_link),
+            ("crag_link", &self.crag_link),
+        ]
+        .into_iter()
+        .filter_map(|(field, link)| match link {
+            Some(TheCragLink::Malformed(text)) => Some(format!("malformed {field}: {text:?}")),
+            _ => None,
+        })
+        .collect()
+    }
+
+    /// Populated fields that have no counterpart in [`OpenTick`]
+    pub(crate) fn dropped_fields(&self) -> Vec<&'static str> {
+        [
+            ("ascent_id", self.ascent_id.is_some()),
+            ("ascent_link", self.ascent_link.is_some()),
+            ("route_height", !self.route_height.is_empty()),
+            ("ascent_height", !self.ascent_height.is_empty()),
+            ("country_link", self.country_link.is_some()),
+            ("crag_link", self.crag_link.is_some()),
+            (
+                "quality",
+                !self.quality.is_empty() && self.quality_percent().is_none(),
+            ),
+            ("log_date", self.log_date.is_some()),
+            ("shot", self.shot.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(field, populated)| populated.then_some(field))
+        .collect()
+    }
+}
+
+/// The parts of theCrag's combined ascent label
+///
+/// See [`TheCragTick::parsed_ascent_label`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TheCragAscentLabel {
+    /// Ascent type the label starts with, if any
+    pub ascent_type: Option<TheCragAscentType>,
+    pub route_name: String,
+    /// Grade the label ends with, if any
+    pub grade: Option<String>,
+}
+
+impl TheCragAscentLabel {
+    /// Split a label of the form `<ascent type> of <route name> <grade>`
+    ///
+    /// The ascent type and grade may each be missing.  The text before the first ` of ` is only
+    /// taken as the ascent type if it is one theCrag allows, since route names can contain "of".
+    /// A trailing word is only taken as the grade if it is a number, as for the Ewbank grades
+    /// theCrag uses in Australia, or is recognized by [`Grade::parse`].  `None` if the label is
+    /// blank.
+    pub fn parse(label: &str) -> Option<TheCragAscentLabel> {
+        let label = label.trim();
+
+        let (ascent_type, rest) = match label.split_once(" of ").map(|(prefix, rest)| {
+            (
+                parse_or_other::<TheCragAscentType>(prefix.to_string()),
+                rest,
+            )
+        }) {
+            Some((ascent_type, rest)) if ascent_type.as_other().is_none() => {
+                (Some(ascent_type), rest)
+            }
+            _ => (None, label),
+        };
+
+        let (route_name, grade) = match rest.rsplit_once(' ') {
+            Some((name, grade))
+                if grade.chars().all(|c| c.is_ascii_digit())
+                    || Grade::parse(grade).system().is_some() =>
+            {
+                (name.trim_end(), Some(grade.to_string()))
+            }
+            _ => (rest, None),
+        };
+
+        (!route_name.is_empty()).then(|| TheCragAscentLabel {
+            ascent_type,
+            route_name: route_name.to_string(),
+            grade,
+        })
+    }
+}
+
+impl Unrecognized for TheCragTick {
+    fn unrecognized_value(&self) -> Option<&str> {
+        self.ascent_type
+            .as_other()
+            .or_else(|| self.route_gear_style.as_other())
+            .or_else(|| self.ascent_gear_style.as_other())
+    }
+}
+
 /// Gear styles allowed by theCrag
 #[non_exhaustive]
-#[derive(Debug, PartialEq, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum TheCragGearStyle {
     Aid,
     Alpine,
@@ -115,11 +313,27 @@ pub enum TheCragGearStyle {
     Unknown,
     #[serde(rename = "")]
     None,
+    /// A gear style not known to this crate
+    #[serde(untagged, skip_deserializing)]
+    Other(String),
+}
+
+impl OrOther for TheCragGearStyle {
+    fn other(value: String) -> Self {
+        TheCragGearStyle::Other(value)
+    }
+
+    fn as_other(&self) -> Option<&str> {
+        match self {
+            TheCragGearStyle::Other(value) => Some(value),
+            _ => None,
+        }
+    }
 }
 
 /// Ascent types allowed by theCrag
 #[non_exhaustive]
-#[derive(Debug, PartialEq, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum TheCragAscentType {
     Aid,
     #[serde(rename = "Aid solo")]
@@ -165,28 +379,392 @@ pub enum TheCragAscentType {
     #[serde(rename = "Top rope with rest")]
     TopRopeWithRest,
     Working,
+    /// An ascent type not known to this crate
+    #[serde(untagged, skip_deserializing)]
+    Other(String),
+}
+
+impl OrOther for TheCragAscentType {
+    fn other(value: String) -> Self {
+        TheCragAscentType::Other(value)
+    }
+
+    fn as_other(&self) -> Option<&str> {
+        match self {
+            TheCragAscentType::Other(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// A link in an export, kept as written if it isn't a valid URL
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(untagged)]
+pub enum TheCragLink {
+    Url(Url),
+    /// Text that doesn't parse as a URL
+    Malformed(String),
+}
+
+impl TheCragLink {
+    /// The URL, if the link is valid
+    pub fn url(&self) -> Option<&Url> {
+        match self {
+            TheCragLink::Url(url) => Some(url),
+            TheCragLink::Malformed(_) => None,
+        }
+    }
+}
+
+impl From<String> for TheCragLink {
+    fn from(value: String) -> Self {
+        match Url::parse(value.trim()) {
+            Ok(url) => TheCragLink::Url(url),
+            Err(_) => TheCragLink::Malformed(value),
+        }
+    }
+}
+
+/// Deserialize a link, with an empty value deserializing to `None`
+///
+/// Links are only informational, so a malformed one shouldn't cost the whole record.
+fn link<'de, D>(deserializer: D) -> Result<Option<TheCragLink>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+
+    Option::<String>::deserialize(deserializer).map(|value| {
+        value
+            .filter(|v| !v.trim().is_empty())
+            .map(TheCragLink::from)
+    })
 }
 
 /// ID of a route in theCrag's database
 ///
-#[derive(Debug, PartialEq, serde::Deserialize)]
-pub struct TheCragRouteId(pub usize);
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+pub struct TheCragRouteId(pub u64);
+
+impl TheCragRouteId {
+    /// ID from a route URL, such as `https://www.thecrag.com/route/12345`
+    pub(crate) fn from_url(url: &Url) -> Option<TheCragRouteId> {
+        if url.domain() != Some("www.thecrag.com") {
+            return None;
+        }
+
+        let mut path = url.path_segments()?;
+        match (path.next(), path.next()) {
+            (Some("route"), Some(id)) => id.parse().ok().map(TheCragRouteId),
+            _ => None,
+        }
+    }
+}
 
 /// ID of an ascent in theCrag's database
 ///
-#[derive(Debug, PartialEq, serde::Deserialize)]
-pub struct TheCragAscentId(pub usize);
-
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-//     use std::convert::TryFrom;
-
-//     #[test]
-//     fn a_tick() {
-//         let t = TheCragTick {
-//         };
-
-//         println!("{t:?}")
-//     }
-// }
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct TheCragAscentId(pub u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+    use pretty_assertions::assert_eq;
+
+    const CRAG_CSV_HEADER: &str = "Route Name,Ascent Label,Ascent ID,Ascent Link,Ascent Type,Route Grade,Ascent Grade,Route Gear Style,Ascent Gear Style,Route Height,Ascent Height,# Ascents,Route Stars,Route ID,Route Link,Country,Country Link,Crag Name,Crag Link,Crag Path,With,Comment,Quality,Ascent Date,Log Date,Shot";
+
+    /// Parse a single row in the column order of [`CRAG_CSV_HEADER`]
+    fn tick_from_row(row: &str) -> TheCragTick {
+        let data = format!("{CRAG_CSV_HEADER}\n{row}\n");
+        let mut reader = csv::Reader::from_reader(data.as_bytes());
+
+        reader
+            .deserialize()
+            .next()
+            .expect("one row")
+            .expect("valid row")
+    }
+
+    #[test]
+    fn from_csv() -> Result<(), Box<dyn std::error::Error>> {
+        let crag_csv = r#"Route Name,Ascent Label,Ascent ID,Ascent Link,Ascent Type,Route Grade,Ascent Grade,Route Gear Style,Ascent Gear Style,Route Height,Ascent Height,# Ascents,Route Stars,Route ID,Route Link,Country,Country Link,Crag Name,Crag Link,Crag Path,With,Comment,Quality,Ascent Date,Log Date,Shot
+Route Name,Red point of Route Name,5069547315,https://www.thecrag.com/ascent/5069547315,Red point,24,24,Sport,Sport,25,25,1,3,12345,https://www.thecrag.com/route/12345,Australia,https://www.thecrag.com/climbing/australia,Arapiles,https://www.thecrag.com/climbing/australia/arapiles,Australia > Victoria > Arapiles,Jo Bloggs,Pumpy,,2023-05-30T00:00:00Z,2023-05-31T10:11:12Z,
+"#;
+
+        let mut ticks = Vec::new();
+        let mut reader = csv::Reader::from_reader(crag_csv.as_bytes());
+
+        for record in reader.deserialize() {
+            let record: TheCragTick = record?;
+
+            assert_eq!(record.route_name, "Route Name");
+            assert_eq!(record.ascent_id, Some(TheCragAscentId(5069547315)));
+            assert_eq!(record.ascent_type, TheCragAscentType::RedPoint);
+            assert_eq!(record.route_gear_style, TheCragGearStyle::Sport);
+            assert_eq!(record.shot, None);
+            let tick = OpenTick::try_from(record.clone()).expect("good tick");
+            assert_eq!(
+                tick.route_id(),
+                Some(crate::RouteRef::TheCrag(TheCragRouteId(12345)))
+            );
+            assert_eq!(tick.partners, Some(vec!["Jo Bloggs".to_string()]));
+
+            ticks.push(record);
+        }
+
+        let mut writer = csv::Writer::from_writer(vec![]);
+        for record in ticks.iter() {
+            writer.serialize(record)?;
+        }
+
+        // Check that record serializes to original, modulo quoting strings with spaces
+        assert_eq!(
+            crag_csv.replace('"', ""),
+            String::from_utf8(writer.into_inner()?)?.replace('"', "")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn blank_and_malformed_links() -> Result<(), ConversionError> {
+        use crate::IntoOpenTick;
+
+        let tick = tick_from_row(
+            "Route Name,,,not a link,Onsight,24,,Sport,Sport,,,1,,12345,,Australia,,Arapiles,https://,Australia > Victoria > Arapiles,,,,2023-05-31T04:30:00Z,,",
+        );
+
+        assert_eq!(
+            tick.ascent_link,
+            Some(TheCragLink::Malformed("not a link".to_string()))
+        );
+        assert_eq!(tick.route_link, None);
+        assert_eq!(tick.country_link, None);
+        assert_eq!(
+            tick.crag_link,
+            Some(TheCragLink::Malformed("https://".to_string()))
+        );
+        assert_eq!(tick.route_id, Some(TheCragRouteId(12345)));
+
+        let converted = tick.into_converted_tick()?;
+        assert_eq!(
+            converted.warnings,
+            [
+                r#"malformed ascent_link: "not a link""#,
+                r#"malformed crag_link: "https://""#
+            ]
+        );
+        assert_eq!(
+            converted.tick.route_url.as_deref(),
+            Some("https://www.thecrag.com/route/12345")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn late_evening_local_date() -> Result<(), ConversionError> {
+        // 21:30 on the 30th in California is 04:30 on the 31st in UTC
+        let tick = tick_from_row(
+            "Route Name,,,,Onsight,5.10a,,Trad,Trad,,,1,,,,USA,,Crag,,USA > California > Crag,,,,2023-05-31T04:30:00Z,,",
+        );
+        let pdt = FixedOffset::west_opt(7 * 3600).expect("valid offset");
+
+        assert_eq!(
+            tick.local_ascent_date(&Utc),
+            NaiveDate::from_ymd_opt(2023, 5, 31)
+        );
+        assert_eq!(
+            tick.into_open_tick_in(&pdt)?.date,
+            NaiveDate::from_ymd_opt(2023, 5, 30)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn location_matches_mountain_project() -> Result<(), Box<dyn std::error::Error>> {
+        let crag_tick = tick_from_row(
+            "Route Name,,,,Onsight,5.9,,Trad,Trad,,,1,,,,United States,,Redgarden Wall,,United States > Colorado > Boulder > Eldorado Canyon > Redgarden Wall,,,,2023-05-31T04:30:00Z,,",
+        );
+        let mp_csv = r#"Date,Route,Rating,Notes,URL,Pitches,Location,"Avg Stars","Your Stars",Style,"Lead Style","Route Type","Your Rating",Length,"Rating Code"
+2023-06-01,"Route Name",5.9,,https://www.mountainproject.com/route/271828/route-name,1,"Colorado > Boulder > Eldorado Canyon SP > Redgarden Wall",2.5,-1,Lead,Onsight,Trad,,10,20300
+"#;
+        let mp_tick: crate::MountainProjectTick = csv::Reader::from_reader(mp_csv.as_bytes())
+            .deserialize()
+            .next()
+            .expect("one row")?;
+
+        let crag_location = OpenTick::try_from(crag_tick)
+            .expect("good tick")
+            .location
+            .unwrap_or_default();
+        let mp_location = OpenTick::try_from(mp_tick)
+            .expect("good tick")
+            .location
+            .unwrap_or_default();
+
+        assert_eq!(crag_location.country.as_deref(), Some("United States"));
+        assert_eq!(crag_location.region.as_deref(), Some("Colorado"));
+        assert_eq!(crag_location.crag.as_deref(), Some("Redgarden Wall"));
+        assert_eq!(
+            (mp_location.country, mp_location.region, mp_location.crag),
+            (
+                crag_location.country,
+                crag_location.region,
+                crag_location.crag
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn ascent_label_parts() {
+        let label = |s: &str| TheCragAscentLabel::parse(s).expect("non-blank label");
+
+        assert_eq!(
+            label("Onsight of Wall of Fire 5.10a"),
+            TheCragAscentLabel {
+                ascent_type: Some(TheCragAscentType::Onsight),
+                route_name: "Wall of Fire".to_string(),
+                grade: Some("5.10a".to_string()),
+            }
+        );
+        assert_eq!(label("Red point of Route Name").route_name, "Route Name");
+        assert_eq!(label("Red point of Route Name").grade, None);
+        assert_eq!(
+            label("Wall of Fire 24"),
+            TheCragAscentLabel {
+                ascent_type: None,
+                route_name: "Wall of Fire".to_string(),
+                grade: Some("24".to_string()),
+            }
+        );
+        assert_eq!(TheCragAscentLabel::parse(""), None);
+    }
+
+    #[test]
+    fn blank_columns_from_label() -> Result<(), ConversionError> {
+        let tick = tick_from_row(
+            ",Hang dog of Route Name 24,,,,,,Sport,Sport,,,1,,,,Australia,,Arapiles,,Australia > Victoria > Arapiles,,,,2023-05-31T04:30:00Z,,",
+        );
+        let tick = OpenTick::try_from(tick)?;
+
+        assert_eq!(tick.route_name.as_deref(), Some("Route Name"));
+        assert_eq!(tick.route_grade.as_deref(), Some("24"));
+        assert_eq!(tick.ascent_style, Some(crate::AscentStyle::Hangdog));
+
+        Ok(())
+    }
+
+    #[test]
+    fn alpine_gear_style() -> Result<(), ConversionError> {
+        let tick = tick_from_row(
+            "Route Name,,,,Onsight,5.8,,Alpine,Alpine,,,1,,,,Canada,,Bugaboos,,Canada > BC > Bugaboos,,,,2023-07-31T04:30:00Z,,",
+        );
+        let tick = OpenTick::try_from(tick)?;
+
+        assert!(tick
+            .route_discipline
+            .is_some_and(|d| d.contains(crate::DisciplineKind::Alpine)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn blank_ascent_grade() -> Result<(), ConversionError> {
+        let tick = OpenTick::try_from(tick_from_row(
+            "Route Name,,,,Red point,21,,Sport,Sport,,,1,,,,Australia,,Arapiles,,Australia > Victoria > Arapiles,,,,2023-05-31T04:30:00Z,,",
+        ))?;
+
+        assert_eq!(tick.ascent_grade, None);
+        assert_eq!(tick.effective_grade(), Some("21"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn quality_percentage() {
+        let tick = tick_from_row(
+            "Route Name,,,,Red point,21,,Trad,Trad,,,1,2,,,Australia,,Arapiles,,Australia > Victoria > Arapiles,,,83%,2023-05-31T04:30:00Z,,",
+        );
+
+        assert_eq!(tick.quality_percent(), Some(83.0));
+        assert_eq!(tick.quality_stars(), Some(4.15));
+        assert_eq!(
+            OpenTick::try_from(tick.clone())
+                .expect("converts")
+                .route_quality,
+            Some(83.0)
+        );
+
+        let blank = TheCragTick {
+            quality: String::new(),
+            ..tick.clone()
+        };
+        let unrated = TheCragTick {
+            quality: "Classic".to_string(),
+            ..tick
+        };
+        assert_eq!(blank.quality_stars(), None);
+        assert_eq!(unrated.quality_stars(), None);
+    }
+
+    #[test]
+    fn route_star_count() {
+        let stars = |route_stars: &str| {
+            TheCragTick {
+            route_stars: route_stars.to_string(),
+            ..tick_from_row(
+                "Route Name,,,,Red point,21,,Trad,Trad,,,1,2,,,Australia,,Arapiles,,Australia > Victoria > Arapiles,,,83%,2023-05-31T04:30:00Z,,",
+            )
+        }
+        };
+
+        assert_eq!(stars("4").route_stars_count(), Some(4));
+        assert_eq!(stars(" 0 ").route_stars_count(), Some(0));
+        assert_eq!(stars("").route_stars_count(), None);
+        assert_eq!(stars("6").route_stars_count(), None);
+        assert_eq!(stars("83%").route_stars_count(), None);
+    }
+
+    #[test]
+    fn trad_route_sport_ascent() -> Result<(), ConversionError> {
+        use crate::{DisciplineKind, IntoOpenTick};
+
+        let tick = tick_from_row(
+            "Route Name,,,,Red point,21,,Trad,Sport,,,1,,,,Australia,,Arapiles,,Australia > Victoria > Arapiles,,,,2023-05-31T04:30:00Z,,",
+        );
+        let converted = tick.into_converted_tick()?;
+        let disciplines =
+            |d: &Option<crate::Discipline>| d.as_ref().map(|d| d.iter().collect::<Vec<_>>());
+
+        assert_eq!(
+            disciplines(&converted.tick.route_discipline),
+            Some(vec![DisciplineKind::Trad])
+        );
+        assert_eq!(
+            disciplines(&converted.tick.ascent_discipline),
+            Some(vec![DisciplineKind::Sport])
+        );
+        assert_eq!(converted.warnings, ["trad route climbed as sport"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_gear_style() {
+        let tick = tick_from_row(
+            "Route Name,,,,Onsight,21,,Jetpack,Trad,,,1,,,,Australia,,Arapiles,,Australia > Victoria > Arapiles,,,,2023-05-31T04:30:00Z,,",
+        );
+
+        assert_eq!(
+            tick.route_gear_style,
+            TheCragGearStyle::Other("Jetpack".to_string())
+        );
+        assert_eq!(tick.ascent_gear_style, TheCragGearStyle::Trad);
+        assert_eq!(tick.unrecognized_value(), Some("Jetpack"));
+    }
+}
@@ -0,0 +1,160 @@
+/// Normalize a route or area name for comparison
+///
+/// Lowercases, strips punctuation, collapses whitespace, and moves a trailing article to the front,
+/// so that names recorded slightly differently on different platforms compare equal.
+///
+/// # Examples
+/// ```
+/// use open_tick::util::normalize_name;
+///
+/// assert_eq!(normalize_name("The Nose"), "the nose");
+/// assert_eq!(normalize_name("Nose, The"), "the nose");
+/// assert_eq!(normalize_name("  the   NOSE! "), "the nose");
+/// ```
+pub fn normalize_name(name: &str) -> String {
+    let name = name.trim();
+
+    // Guidebooks often sort names by moving the article to the end, e.g. "Nose, The"
+    let name = match name.rsplit_once(',') {
+        Some((rest, article)) if is_article(article.trim()) => {
+            format!("{} {}", article.trim(), rest)
+        }
+        _ => name.to_string(),
+    };
+
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_lowercase().next().unwrap_or(c)
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Similarity of two route names from 0 to 1, by edit distance between their normalized forms
+///
+/// Names are normalized as by [`normalize_name`], so names that differ only in case,
+/// punctuation or a trailing article score 1.  Otherwise the score falls with the Levenshtein
+/// distance, relative to the length of the longer name, so a typo in a long name costs less
+/// than in a short one.
+///
+/// # Examples
+/// ```
+/// use open_tick::util::name_similarity;
+///
+/// assert_eq!(name_similarity("The Nose", "Nose, The"), 1.0);
+///
+/// let typo = name_similarity("Salathé Wall", "Salathe Wall");
+/// let typos = name_similarity("Salathé Wall", "Salathe Wal");
+/// let other = name_similarity("Salathé Wall", "Freerider");
+/// assert!(typo > 0.9);
+/// assert!(typo > typos && typos > other);
+/// ```
+pub fn name_similarity(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = normalize_name(a).chars().collect();
+    let b: Vec<char> = normalize_name(b).chars().collect();
+
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    1.0 - levenshtein(&a, &b) as f32 / longest as f32
+}
+
+/// Number of single character insertions, deletions and substitutions turning `a` into `b`
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+fn is_article(word: &str) -> bool {
+    ["the", "a", "an"].contains(&word.to_lowercase().as_str())
+}
+
+/// Split a location into its hierarchy of areas, from broadest to most specific
+///
+/// Both Mountain Project and theCrag separate areas with `" > "`.
+///
+/// # Examples
+/// ```
+/// use open_tick::util::parse_area_path;
+///
+/// assert_eq!(
+///     parse_area_path("Australia > Victoria > Arapiles"),
+///     vec!["Australia", "Victoria", "Arapiles"]
+/// );
+/// ```
+pub fn parse_area_path(location: &str) -> Vec<String> {
+    location
+        .split('>')
+        .map(str::trim)
+        .filter(|area| !area.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Number of attempts recorded in a comment with a tag like `#attempts:5`
+///
+/// The tag is matched ignoring case.  `None` if there is no such tag.
+///
+/// # Examples
+/// ```
+/// use open_tick::util::parse_attempts_tag;
+///
+/// assert_eq!(parse_attempts_tag("Finally! #attempts:5"), Some(5));
+/// assert_eq!(parse_attempts_tag("#Attempts:12 so pumped"), Some(12));
+/// assert_eq!(parse_attempts_tag("Took a few attempts"), None);
+/// ```
+pub fn parse_attempts_tag(comment: &str) -> Option<u32> {
+    comment.split_whitespace().find_map(|word| {
+        let (tag, count) = word.split_once(':')?;
+        tag.eq_ignore_ascii_case("#attempts")
+            .then(|| count.parse().ok())
+            .flatten()
+    })
+}
+
+/// Number of the pitch a comment is about, as in `Pitch 2` or `P2: wet`
+///
+/// Climbers who log a multi-pitch route one row per pitch often number the pitches this way.
+/// Matched ignoring case and surrounding punctuation.  `None` if no pitch is named.
+///
+/// # Examples
+/// ```
+/// use open_tick::util::parse_pitch_number;
+///
+/// assert_eq!(parse_pitch_number("Pitch 3, the crux"), Some(3));
+/// assert_eq!(parse_pitch_number("P2: wet"), Some(2));
+/// assert_eq!(parse_pitch_number("Pumpy pitch"), None);
+/// ```
+pub fn parse_pitch_number(comment: &str) -> Option<u32> {
+    let words: Vec<&str> = comment
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| c.is_ascii_punctuation()))
+        .collect();
+
+    words.iter().enumerate().find_map(|(i, word)| {
+        let number = match word.get(..1) {
+            _ if word.eq_ignore_ascii_case("pitch") => words.get(i + 1)?,
+            Some("P" | "p") => &word[1..],
+            _ => return None,
+        };
+        number.parse().ok()
+    })
+}
@@ -0,0 +1,145 @@
+use crate::de::{or_other, OrOther, Unrecognized};
+use crate::PartialDate;
+
+/// A tick as recorded in a CSV export of a Vertical-Life logbook
+///
+/// Vertical-Life logs both gym sessions, including climbs on training boards, and outdoor climbs.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct VerticalLifeTick {
+    /// Climbs logged long after the fact may give only the year or month
+    #[serde(rename = "Date")]
+    pub date: Option<PartialDate>,
+
+    #[serde(rename = "Name")]
+    pub name: String,
+
+    /// Grade as shown in the app, usually French for routes and Fontainebleau for boulders
+    #[serde(rename = "Grade")]
+    pub grade: String,
+
+    #[serde(rename = "Type", deserialize_with = "or_other")]
+    pub climb_type: VerticalLifeType,
+
+    #[serde(rename = "Style", deserialize_with = "or_other")]
+    pub style: VerticalLifeStyle,
+
+    /// Number of tries it took, if recorded
+    #[serde(rename = "Tries")]
+    pub tries: Option<u32>,
+
+    /// Name of the gym or crag
+    #[serde(rename = "Location")]
+    pub location: String,
+
+    /// Whether the climb was in a gym
+    #[serde(rename = "Indoor")]
+    pub indoor: Option<bool>,
+
+    /// Name of the training board the climb was set on, empty if none
+    #[serde(rename = "Board")]
+    pub board: String,
+
+    /// Angle of the training board, in degrees past vertical
+    #[serde(rename = "Board Angle")]
+    pub board_angle: Option<u8>,
+
+    #[serde(rename = "Notes")]
+    pub notes: String,
+}
+
+impl Unrecognized for VerticalLifeTick {
+    fn unrecognized_value(&self) -> Option<&str> {
+        self.climb_type.as_other().or_else(|| self.style.as_other())
+    }
+}
+
+/// Types of climb recorded by Vertical-Life
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum VerticalLifeType {
+    Boulder,
+    /// A roped route, led
+    Sport,
+    TopRope,
+    /// A type not known to this crate
+    #[serde(untagged, skip_deserializing)]
+    Other(String),
+}
+
+impl OrOther for VerticalLifeType {
+    fn other(value: String) -> Self {
+        VerticalLifeType::Other(value)
+    }
+
+    fn as_other(&self) -> Option<&str> {
+        match self {
+            VerticalLifeType::Other(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Styles of ascent recorded by Vertical-Life
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum VerticalLifeStyle {
+    Onsight,
+    Flash,
+    Redpoint,
+    /// Topped, without further detail
+    Top,
+    /// Didn't top
+    Attempt,
+    /// A style not known to this crate
+    #[serde(untagged, skip_deserializing)]
+    Other(String),
+}
+
+impl OrOther for VerticalLifeStyle {
+    fn other(value: String) -> Self {
+        VerticalLifeStyle::Other(value)
+    }
+
+    fn as_other(&self) -> Option<&str> {
+        match self {
+            VerticalLifeStyle::Other(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn from_csv() -> Result<(), Box<dyn std::error::Error>> {
+        let vl_csv = "Date,Name,Grade,Type,Style,Tries,Location,Indoor,Board,Board Angle,Notes
+2023-06-01,Crimp Ladder,6B+,Boulder,Top,4,Boulderwelt,true,MoonBoard 2016,40,
+2023-06-03,,7a,Sport,Hangdog,,Frankenjura,false,,,Next time
+2019-06,Old Project,7b,Sport,Redpoint,,Frankenjura,false,,,
+";
+        let mut reader = csv::Reader::from_reader(vl_csv.as_bytes());
+        let ticks = reader
+            .deserialize()
+            .collect::<Result<Vec<VerticalLifeTick>, _>>()?;
+
+        assert_eq!(ticks[0].style, VerticalLifeStyle::Top);
+        assert_eq!(ticks[0].tries, Some(4));
+        assert_eq!(ticks[0].board_angle, Some(40));
+        assert_eq!(ticks[1].tries, None);
+        assert_eq!(
+            ticks[1].style,
+            VerticalLifeStyle::Other("Hangdog".to_string())
+        );
+        assert_eq!(ticks[1].unrecognized_value(), Some("Hangdog"));
+        assert_eq!(ticks[2].date, Some(PartialDate::YearMonth(2019, 6)));
+
+        let old = crate::OpenTick::try_from(ticks[2].clone()).expect("good tick");
+        assert_eq!(old.date, None);
+        assert_eq!(old.partial_date, Some(PartialDate::YearMonth(2019, 6)));
+
+        Ok(())
+    }
+}
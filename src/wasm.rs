@@ -0,0 +1,36 @@
+use crate::import::collect_ticks;
+use crate::Source;
+use wasm_bindgen::prelude::*;
+
+/// Parse a Mountain Project CSV export into an array of serialized [`OpenTick`](crate::OpenTick)s
+///
+/// Errors are returned as strings.
+#[wasm_bindgen(js_name = parseMountainProjectCsv)]
+pub fn parse_mountain_project_csv(data: &str) -> Result<JsValue, JsValue> {
+    let ticks = collect_ticks(data.as_bytes(), Source::MountainProject)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    Ok(serde_wasm_bindgen::to_value(&ticks)?)
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn parse_csv() {
+        let mp_csv = r#"Date,Route,Rating,Notes,URL,Pitches,Location,"Avg Stars","Your Stars",Style,"Lead Style","Route Type","Your Rating",Length,"Rating Code"
+2023-06-01,"Route Name",V1,,https://www.mountainproject.com/route/271828/route-name,1,"Area > Crag",2.5,-1,Send,,Boulder,,10,20300
+"#;
+
+        let ticks = parse_mountain_project_csv(mp_csv).expect("valid export");
+
+        assert_eq!(js_sys::Array::from(&ticks).length(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn parse_bad_csv() {
+        assert!(parse_mountain_project_csv("Date\nnot a date\n").is_err());
+    }
+}
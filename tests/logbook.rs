@@ -0,0 +1,108 @@
+#![cfg(feature = "serde")]
+
+use open_tick::import::collect_ticks;
+use open_tick::logbook::Logbook;
+use open_tick::Source;
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+
+#[test]
+fn from_exports_of_two_platforms() -> Result<(), Box<dyn Error>> {
+    let logbook = Logbook::from_paths(&[
+        Path::new("tests/fixtures/mountain_project_api.json"),
+        Path::new("tests/fixtures/thecrag_yosemite.csv"),
+    ])?;
+
+    let names: Vec<_> = logbook
+        .iter()
+        .map(|t| (t.route_name.as_deref(), t.source))
+        .collect();
+    assert_eq!(
+        names,
+        [
+            (Some("The Nose"), Some(Source::MountainProject)),
+            (Some("Midnight Lightning"), Some(Source::MountainProject)),
+            (Some("Serenity Crack"), Some(Source::TheCrag)),
+        ]
+    );
+
+    // Both platforms logged the Nose; the merged tick keeps what only theCrag recorded
+    assert_eq!(logbook[0].route_quality, Some(95.0));
+
+    Ok(())
+}
+
+#[test]
+fn from_unrecognized_export() {
+    let result = Logbook::from_paths(&[Path::new("tests/fixtures/training.json")]);
+
+    assert!(matches!(
+        result,
+        Err(open_tick::import::ImportError::UnknownSource)
+    ));
+}
+
+#[test]
+fn reimport_adds_nothing() -> Result<(), Box<dyn Error>> {
+    let export = || -> Result<_, Box<dyn Error>> {
+        Ok(collect_ticks(
+            File::open("tests/fixtures/vertical_life.csv")?,
+            Source::VerticalLife,
+        )?)
+    };
+    let mut logbook = Logbook::default();
+
+    assert_eq!(logbook.import_new(export()?.into_iter()), 3);
+    assert_eq!(logbook.import_new(export()?.into_iter()), 0);
+    assert_eq!(logbook.len(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn from_directory_of_exports() -> Result<(), Box<dyn Error>> {
+    let dir = std::env::temp_dir().join(format!("open_tick-exports-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    for fixture in [
+        "mountain_project_api.json",
+        "padded_mountain_project.csv",
+        "vertical_life.csv",
+        "training.json",
+    ] {
+        std::fs::copy(Path::new("tests/fixtures").join(fixture), dir.join(fixture))?;
+    }
+    std::fs::write(dir.join("notes.txt"), "Date,Route\n")?;
+
+    let imported = Logbook::from_dir(&dir);
+    std::fs::remove_dir_all(&dir)?;
+    let (logbook, skipped) = imported?;
+
+    let sources: Vec<_> = logbook.iter().map(|t| t.source).collect();
+    assert_eq!(
+        sources,
+        [
+            Some(Source::MountainProject),
+            Some(Source::MountainProject),
+            Some(Source::MountainProject),
+            Some(Source::VerticalLife),
+            Some(Source::VerticalLife),
+            Some(Source::VerticalLife),
+        ]
+    );
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].0, dir.join("training.json"));
+    assert!(matches!(
+        skipped[0].1,
+        open_tick::import::ImportError::UnknownSource
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn from_missing_directory() {
+    let result = Logbook::from_dir(Path::new("tests/fixtures/no-such-directory"));
+
+    assert!(matches!(result, Err(open_tick::import::ImportError::Io(_))));
+}
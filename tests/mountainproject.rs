@@ -1,7 +1,11 @@
-use open_tick::{MountainProjectTick, OpenTick};
+#![cfg(feature = "serde")]
+
+use open_tick::mountain_project::MountainProjectApiTick;
+use open_tick::{AscentStyle, DisciplineKind, IntoOpenTick, MountainProjectTick, OpenTick};
 use std::error::Error;
 
 #[test]
+#[allow(clippy::len_zero)]
 fn parse_csv() -> Result<(), Box<dyn Error>> {
     let mut rdr = csv::ReaderBuilder::new()
         .flexible(true)
@@ -22,3 +26,92 @@ fn parse_csv() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+fn collect_csv() -> Result<(), Box<dyn Error>> {
+    let file = std::fs::File::open(
+        "test-data/open-tick-list/test-data/mountainproject/ticks-2023-06-01.csv",
+    )?;
+
+    let ticks = open_tick::import::collect_ticks(file, open_tick::Source::MountainProject)?;
+
+    assert!(!ticks.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn padded_cells() -> Result<(), Box<dyn Error>> {
+    let file = std::fs::File::open("tests/fixtures/padded_mountain_project.csv")?;
+
+    let ticks = open_tick::import::collect_ticks(file, open_tick::Source::MountainProject)?;
+
+    assert_eq!(ticks[0].route_name.as_deref(), Some("Route"));
+    assert_eq!(ticks[0].route_grade.as_deref(), Some("5.10a"));
+    assert_eq!(ticks[0].comment.as_deref(), Some("Edited by hand"));
+    assert_eq!(ticks[0].route_location.as_deref(), Some("Area > Crag"));
+    assert_eq!(ticks[0].ascent_style, Some(open_tick::AscentStyle::Onsight));
+
+    Ok(())
+}
+
+/// Cells that overflow or don't parse as their field's type fail their row without panicking
+#[test]
+fn malformed_cells() {
+    let csv = r#"Date,Route,Rating,Notes,URL,Pitches,Location,"Avg Stars","Your Stars",Style,"Lead Style","Route Type","Your Rating",Length,"Rating Code"
+2023-06-01,Route,V1,,https://www.mountainproject.com/route/1/route,99999,"Area > Crag",2.5,-1,Send,,Boulder,,10,20300
+2023-06-01,Route,V1,,https://www.mountainproject.com/route/1/route,1,"Area > Crag",2.5,999,Send,,Boulder,,10,20300
+2023-06-01,Route,V1,,not a url,1,"Area > Crag",2.5,-1,Send,,Boulder,,10,20300
+2023-06-01,Route,V1,,https://www.mountainproject.com/route/1/route,1,"Area > Crag",2.5,-1,Send,,Boulder,,-10,20300
+2023-13-01,Route,V1,,https://www.mountainproject.com/route/1/route,1,"Area > Crag",2.5,-1,Send,,Boulder,,10,20300
+2023-06-01,Route,V1,,https://www.mountainproject.com/route/1/route,1,"Area > Crag",2.5,-1,Send,,Boulder,,10,20300
+"#;
+
+    let (ticks, errors) =
+        open_tick::import::collect_ticks_lossy(csv.as_bytes(), open_tick::Source::MountainProject);
+
+    assert_eq!(ticks.len(), 1);
+    assert_eq!(errors.len(), 5, "{errors:?}");
+}
+
+#[test]
+fn parse_api_json() -> Result<(), Box<dyn Error>> {
+    #[derive(serde::Deserialize)]
+    struct Response {
+        ticks: Vec<MountainProjectApiTick>,
+    }
+
+    let response: Response = serde_json::from_reader(std::fs::File::open(
+        "tests/fixtures/mountain_project_api.json",
+    )?)?;
+    let ticks = response
+        .ticks
+        .iter()
+        .cloned()
+        .map(IntoOpenTick::into_open_tick)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(ticks.len(), 2);
+    assert_eq!(ticks[0].route_name.as_deref(), Some("The Nose"));
+    assert_eq!(
+        ticks[0].route_location.as_deref(),
+        Some("California > Yosemite National Park > Yosemite Valley > El Capitan")
+    );
+    assert_eq!(ticks[0].ascent_style, Some(AscentStyle::Redpoint));
+    assert_eq!(ticks[0].pitches, Some(31));
+    assert_eq!(
+        ticks[1].route_url.as_deref(),
+        Some("https://www.mountainproject.com/route/105862930")
+    );
+    assert_eq!(ticks[1].ascent_grade, None);
+    assert!(ticks[1]
+        .route_discipline
+        .as_ref()
+        .is_some_and(|d| d.contains(DisciplineKind::Bouldering)));
+    assert_eq!(ticks[1].ascent_style, Some(AscentStyle::Send));
+
+    let converted = response.ticks[0].clone().into_converted_tick()?;
+    assert_eq!(converted.dropped, ["tick_id", "your_stars"]);
+
+    Ok(())
+}
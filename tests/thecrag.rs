@@ -1,7 +1,11 @@
-use open_tick::{OpenTick, TheCragTick};
+#![cfg(feature = "serde")]
+
+use open_tick::import::collect_ticks;
+use open_tick::{AscentStyle, OpenTick, Source, TheCragTick};
 use std::error::Error;
 
 #[test]
+#[allow(clippy::len_zero)]
 fn parse_csv() -> Result<(), Box<dyn Error>> {
     let mut rdr = csv::ReaderBuilder::new()
         .flexible(true)
@@ -22,3 +26,27 @@ fn parse_csv() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+fn reordered_columns() -> Result<(), Box<dyn Error>> {
+    let canonical = r#"Route Name,Ascent Label,Ascent ID,Ascent Link,Ascent Type,Route Grade,Ascent Grade,Route Gear Style,Ascent Gear Style,Route Height,Ascent Height,# Ascents,Route Stars,Route ID,Route Link,Country,Country Link,Crag Name,Crag Link,Crag Path,With,Comment,Quality,Ascent Date,Log Date,Shot
+Route Name,Red point of Route Name,5069547315,https://www.thecrag.com/ascent/5069547315,Red point,24,24,Sport,Sport,25,25,1,3,12345,https://www.thecrag.com/route/12345,Australia,https://www.thecrag.com/climbing/australia,Arapiles,https://www.thecrag.com/climbing/australia/arapiles,Australia > Victoria > Arapiles,Jo Bloggs,Pumpy,83%,2023-05-30T00:00:00Z,2023-05-31T10:11:12Z,
+Another Route,Onsight of Another Route,5069547316,,Onsight,18,,Trad,Trad,40,40,1,2,12346,,Australia,,Arapiles,,Australia > Victoria > Arapiles,,Classic,,2023-05-31T00:00:00Z,,
+"#;
+    let records = |csv: &str| -> Result<Vec<TheCragTick>, csv::Error> {
+        csv::Reader::from_reader(csv.as_bytes())
+            .into_deserialize()
+            .collect()
+    };
+    let reordered = std::fs::read_to_string("tests/fixtures/thecrag_reordered.csv")?;
+
+    assert_eq!(records(&reordered)?, records(canonical)?);
+
+    let ticks = collect_ticks(reordered.as_bytes(), Source::TheCrag)?;
+    assert_eq!(ticks.len(), 2);
+    assert_eq!(ticks[0].route_name.as_deref(), Some("Route Name"));
+    assert_eq!(ticks[0].partners, Some(vec!["Jo Bloggs".to_string()]));
+    assert_eq!(ticks[1].ascent_style, Some(AscentStyle::Onsight));
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "serde")]
+
+use open_tick::grade::{GradePreferences, GradeSystem};
+use open_tick::import::{collect_ticks, parse_str, IterExt};
+use open_tick::logbook::Logbook;
+use open_tick::{stats, AscentStyle, DisciplineKind, Source, VerticalLifeTick};
+use std::error::Error;
+use std::fs::File;
+
+#[test]
+fn collect_csv() -> Result<(), Box<dyn Error>> {
+    let ticks = collect_ticks(
+        File::open("tests/fixtures/vertical_life.csv")?,
+        Source::VerticalLife,
+    )?;
+
+    assert_eq!(ticks.len(), 3);
+    assert_eq!(ticks[0].indoor, Some(true));
+    assert_eq!(
+        ticks[0].board.as_ref().map(|b| (b.name.as_str(), b.angle)),
+        Some(("MoonBoard 2016", Some(40)))
+    );
+    assert_eq!(ticks[1].route_name, None);
+    assert_eq!(ticks[2].ascent_style, Some(AscentStyle::Redpoint));
+    assert_eq!(
+        ticks[2].location.as_ref().and_then(|l| l.crag.as_deref()),
+        Some("Rabenfels")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn open_ticks_from_reader() -> Result<(), Box<dyn Error>> {
+    let mut reader = csv::Reader::from_path("tests/fixtures/vertical_life.csv")?;
+
+    let names = reader
+        .deserialize::<VerticalLifeTick>()
+        .open_ticks()
+        .map(|tick| tick.map(|t| t.route_name))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(
+        names,
+        collect_ticks(
+            File::open("tests/fixtures/vertical_life.csv")?,
+            Source::VerticalLife
+        )?
+        .into_iter()
+        .map(|t| t.route_name)
+        .collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn logbook_filter_then_stat() -> Result<(), Box<dyn Error>> {
+    let logbook = Logbook::from(collect_ticks(
+        File::open("tests/fixtures/vertical_life.csv")?,
+        Source::VerticalLife,
+    )?);
+
+    let boulders = logbook
+        .clone()
+        .filter_discipline(DisciplineKind::Bouldering);
+    assert_eq!(boulders.len(), 2);
+    assert_eq!(stats::onsight_flash_ratio(&boulders), 0.0);
+
+    let sport = logbook.filter_discipline(DisciplineKind::Sport);
+    assert_eq!(
+        sport
+            .hardest_send(
+                &GradePreferences::default().with(DisciplineKind::Sport, GradeSystem::French)
+            )
+            .and_then(|t| t.route_name.as_deref()),
+        Some("Sautanz")
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_matches_sequential() -> Result<(), Box<dyn Error>> {
+    use open_tick::import::par_collect_ticks;
+
+    let path = "tests/fixtures/vertical_life.csv";
+    let sequential = collect_ticks(File::open(path)?, Source::VerticalLife)?;
+    let parallel = par_collect_ticks(File::open(path)?, Source::VerticalLife)?;
+
+    assert_eq!(parallel, sequential);
+
+    Ok(())
+}
+
+#[test]
+fn parse_in_memory_export() -> Result<(), Box<dyn Error>> {
+    let export = std::fs::read_to_string("tests/fixtures/vertical_life.csv")?;
+
+    let ticks = parse_str(&export, Source::VerticalLife)?;
+
+    assert_eq!(ticks.len(), 3);
+    assert!(ticks.iter().all(|t| t.source == Some(Source::VerticalLife)));
+
+    Ok(())
+}